@@ -0,0 +1,128 @@
+//! actix-web extractors and responses for blobs
+
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::error::{ErrorBadRequest, PayloadError};
+use actix_web::http::header;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder};
+use futures_core::Stream;
+
+use super::stream::StreamDecoder;
+use super::{Blob, Config};
+
+/// Default limit of a base-64 request body, as for actix-web's own `PayloadConfig`
+const DEFAULT_LIMIT: usize = 262_144;
+
+/// Limit of the encoded request body when extracting a `Blob`, set with `App::app_data`
+///
+/// Longer bodies are rejected with `413 Payload Too Large`. Without this, the limit is 256 KiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobPayloadConfig {
+    limit: usize,
+}
+
+impl BlobPayloadConfig {
+    /// Sets the maximum length of the encoded body, in bytes
+    #[inline]
+    pub fn limit(mut self, limit: usize) -> BlobPayloadConfig {
+        self.limit = limit;
+        self
+    }
+}
+
+impl Default for BlobPayloadConfig {
+    #[inline]
+    fn default() -> BlobPayloadConfig {
+        BlobPayloadConfig {
+            limit: DEFAULT_LIMIT,
+        }
+    }
+}
+
+/// Future decoding a `Blob` from a request body, as returned by `Blob::from_request`
+///
+/// The body is decoded as it arrives, skipping ASCII whitespace as with
+/// `Blob::decode_base64_lenient`, so the encoded text is never buffered in full.
+pub struct BlobExtractFut<C: Config> {
+    payload: Payload,
+    decoder: StreamDecoder<C>,
+    remaining: usize,
+    too_long: bool,
+}
+
+impl<C: Config> Future for BlobExtractFut<C> {
+    type Output = Result<Blob<C>, actix_web::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.too_long {
+            return Poll::Ready(Err(PayloadError::Overflow.into()));
+        }
+
+        loop {
+            let chunk = match Pin::new(&mut this.payload).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Ok(chunk))) => chunk,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err.into())),
+                Poll::Ready(None) => {
+                    let decoder = mem::replace(&mut this.decoder, StreamDecoder::new());
+
+                    return Poll::Ready(decoder.finish().map_err(ErrorBadRequest));
+                }
+            };
+
+            this.remaining = match this.remaining.checked_sub(chunk.len()) {
+                Some(remaining) => remaining,
+                None => return Poll::Ready(Err(PayloadError::Overflow.into())),
+            };
+
+            if let Err(err) = this.decoder.write(&chunk) {
+                return Poll::Ready(Err(ErrorBadRequest(err)));
+            }
+        }
+    }
+}
+
+/// Decodes a base-64 (or `C`-encoded) request body, up to the limit of a `BlobPayloadConfig`
+///
+/// Invalid text is rejected with `400 Bad Request`.
+impl<C: Config> FromRequest for Blob<C> {
+    type Error = actix_web::Error;
+    type Future = BlobExtractFut<C>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> BlobExtractFut<C> {
+        let limit = req
+            .app_data::<BlobPayloadConfig>()
+            .map_or(DEFAULT_LIMIT, |config| config.limit);
+
+        let too_long = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|len| len.to_str().ok()?.parse::<usize>().ok())
+            .is_some_and(|len| len > limit);
+
+        BlobExtractFut {
+            payload: payload.take(),
+            decoder: StreamDecoder::new(),
+            remaining: limit,
+            too_long,
+        }
+    }
+}
+
+/// Sends the raw bytes as `application/octet-stream`, without copying them
+impl<C: Config> Responder for Blob<C> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<BoxBody> {
+        HttpResponse::Ok()
+            .content_type("application/octet-stream")
+            .body(self.into_vec())
+    }
+}
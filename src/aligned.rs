@@ -0,0 +1,288 @@
+//! Blobs whose bytes start at a guaranteed alignment
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::slice;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Error};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Alignment of the bytes of an `AlignedBlob`, one of `Align16`, `Align64` or `Align4096`
+///
+/// Each alignment is a block of bytes as large as it is aligned, which the blob is stored in.
+pub trait Alignment: sealed::Sealed + Copy + Send + Sync + 'static {
+    /// A block of zeroed bytes
+    #[doc(hidden)]
+    const ZERO: Self;
+}
+
+macro_rules! impl_alignments {
+    ($($name:ident: $align:literal;)*) => {
+        $(
+            #[doc = concat!("Aligns the bytes of an `AlignedBlob` to ", $align, " bytes")]
+            #[derive(Debug, Clone, Copy)]
+            #[repr(C, align($align))]
+            pub struct $name([u8; $align]);
+
+            impl sealed::Sealed for $name {}
+
+            impl Alignment for $name {
+                const ZERO: $name = $name([0; $align]);
+            }
+        )*
+    };
+}
+
+impl_alignments! {
+    Align16: 16;
+    Align64: 64;
+    Align4096: 4096;
+}
+
+/// Blob structure whose bytes always start at the alignment of `A`
+///
+/// The bytes can be handed directly to SIMD kernels or `O_DIRECT` I/O without a realigning
+/// copy. Decoding goes through a temporary buffer before the bytes are aligned. It encodes,
+/// displays and serializes exactly as a `Blob<C>`.
+pub struct AlignedBlob<C: Config, A: Alignment> {
+    blocks: Vec<A>,
+    len: usize,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config, A: Alignment> AlignedBlob<C, A> {
+    /// Create a new empty `AlignedBlob`
+    #[inline]
+    pub fn new() -> AlignedBlob<C, A> {
+        AlignedBlob {
+            blocks: Vec::new(),
+            len: 0,
+            _config: PhantomData,
+        }
+    }
+
+    /// Create an `AlignedBlob` holding a copy of the given bytes
+    pub fn from_slice(bytes: &[u8]) -> AlignedBlob<C, A> {
+        let mut blob = AlignedBlob::new();
+
+        blob.extend_from_slice(bytes);
+
+        blob
+    }
+
+    /// Returns the alignment of the bytes
+    #[inline]
+    pub fn alignment() -> usize {
+        mem::align_of::<A>()
+    }
+
+    /// Decode an `AlignedBlob` from the given string, as `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<AlignedBlob<C, A>, Error> {
+        Blob::decode_base64(encoded).map(AlignedBlob::from)
+    }
+
+    /// Encode the `AlignedBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(self, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(self, writer)
+    }
+
+    /// Appends the given bytes, keeping the start of the bytes aligned
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let start = self.len;
+        let len = start + bytes.len();
+
+        self.blocks
+            .resize(len.div_ceil(mem::size_of::<A>()), A::ZERO);
+
+        self.capacity_mut()[start..len].copy_from_slice(bytes);
+        self.len = len;
+    }
+
+    /// Removes all bytes, keeping the allocation
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self[..])
+    }
+
+    /// Returns every allocated byte, including those past the length
+    #[inline]
+    fn capacity_mut(&mut self) -> &mut [u8] {
+        let capacity = self.blocks.len() * mem::size_of::<A>();
+
+        // alignments are sealed, and each is a plain array of bytes without padding
+        unsafe { slice::from_raw_parts_mut(self.blocks.as_mut_ptr() as *mut u8, capacity) }
+    }
+}
+
+impl<C: Config, A: Alignment> Default for AlignedBlob<C, A> {
+    #[inline]
+    fn default() -> AlignedBlob<C, A> {
+        AlignedBlob::new()
+    }
+}
+
+impl<C: Config, A: Alignment> Clone for AlignedBlob<C, A> {
+    #[inline]
+    fn clone(&self) -> AlignedBlob<C, A> {
+        AlignedBlob {
+            blocks: self.blocks.clone(),
+            len: self.len,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config, A: Alignment> fmt::Debug for AlignedBlob<C, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AlignedBlob").field(&&self[..]).finish()
+    }
+}
+
+impl<C: Config, A: Alignment> Display for AlignedBlob<C, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(self, f)
+    }
+}
+
+/// Decodes an `AlignedBlob` from a string, as `Blob` would
+impl<C: Config, A: Alignment> FromStr for AlignedBlob<C, A> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(AlignedBlob::from)
+    }
+}
+
+impl<C: Config, A: Alignment> PartialEq for AlignedBlob<C, A> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<C: Config, A: Alignment> Eq for AlignedBlob<C, A> {}
+
+impl<C: Config, A: Alignment> PartialEq<Blob<C>> for AlignedBlob<C, A> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<C: Config, A: Alignment> Hash for AlignedBlob<C, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state);
+    }
+}
+
+impl<C: Config, A: Alignment> Deref for AlignedBlob<C, A> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        // as in `capacity_mut`, limited to the initialized length
+        unsafe { slice::from_raw_parts(self.blocks.as_ptr() as *const u8, self.len) }
+    }
+}
+
+impl<C: Config, A: Alignment> DerefMut for AlignedBlob<C, A> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        let len = self.len;
+
+        &mut self.capacity_mut()[..len]
+    }
+}
+
+impl<C: Config, A: Alignment> AsRef<[u8]> for AlignedBlob<C, A> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<C: Config, A: Alignment> AsMut<[u8]> for AlignedBlob<C, A> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl<'a, C: Config, A: Alignment> From<&'a [u8]> for AlignedBlob<C, A> {
+    #[inline]
+    fn from(bytes: &'a [u8]) -> AlignedBlob<C, A> {
+        AlignedBlob::from_slice(bytes)
+    }
+}
+
+impl<C: Config, A: Alignment> From<Blob<C>> for AlignedBlob<C, A> {
+    #[inline]
+    fn from(blob: Blob<C>) -> AlignedBlob<C, A> {
+        AlignedBlob::from_slice(&blob)
+    }
+}
+
+impl<C: Config, A: Alignment> From<AlignedBlob<C, A>> for Blob<C> {
+    #[inline]
+    fn from(blob: AlignedBlob<C, A>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, A: Alignment> serde::Serialize for AlignedBlob<C, A> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config, A: Alignment> serde::Deserialize<'de> for AlignedBlob<C, A> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(AlignedBlob::from)
+    }
+}
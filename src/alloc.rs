@@ -0,0 +1,498 @@
+//! Blobs stored in a custom allocator
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::any;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::io;
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::Vec as AllocVec;
+
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+use super::in_place::{decode_chunk, decode_front, DecodeFn};
+#[cfg(feature = "serde")]
+use super::serde_impl::{check_raw_len, BlobVisitor, SeqByte};
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+#[cfg(feature = "std")]
+use super::stream::{invalid_data, read_into, StreamDecoder};
+#[cfg(feature = "serde")]
+use super::whitespace;
+use super::{Blob, Config, Error, Hex, Standard};
+
+/// Number of symbols decoded at a time, a multiple of the base-64, base-32 and hex quanta
+const CHUNK_LEN: usize = 1024;
+
+/// Blob structure storing its binary data in the allocator `A`, such as a per-request arena
+///
+/// Each constructor and decoder takes the allocator as with the `*_in` methods of
+/// `allocator_api2`, and `seed_in` deserializes into one. Configs with an
+/// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN) decode
+/// through a small scratch buffer, while others decode into a temporary `Blob` first. It
+/// encodes, displays and serializes exactly as a `Blob<C>`.
+pub struct AllocBlob<C: Config = Standard, A: Allocator = Global> {
+    data: AllocVec<u8, A>,
+    _config: PhantomData<fn() -> C>,
+}
+
+/// `AllocBlob` carved from a `bumpalo` arena, freed all at once when the arena is reset or dropped
+///
+/// Decoding with `BumpBlob::decode_base64_in(encoded, &bump)` replaces a `malloc` and `free` per
+/// blob with a pointer bump, for parsers decoding many small, short-lived blobs.
+#[cfg(feature = "bumpalo")]
+pub type BumpBlob<'bump, C = Standard> = AllocBlob<C, &'bump Bump>;
+
+impl<C: Config, A: Allocator> AllocBlob<C, A> {
+    /// Create a new empty `AllocBlob` in the given allocator
+    #[inline]
+    pub fn new_in(alloc: A) -> AllocBlob<C, A> {
+        AllocBlob::from_vec(AllocVec::new_in(alloc))
+    }
+
+    /// Create a new empty `AllocBlob` with the given capacity, in the given allocator
+    #[inline]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> AllocBlob<C, A> {
+        AllocBlob::from_vec(AllocVec::with_capacity_in(capacity, alloc))
+    }
+
+    /// Create an `AllocBlob` holding a copy of the given bytes, in the given allocator
+    pub fn from_slice_in(bytes: &[u8], alloc: A) -> AllocBlob<C, A> {
+        let mut blob = AllocBlob::with_capacity_in(bytes.len(), alloc);
+
+        blob.data.extend_from_slice(bytes);
+
+        blob
+    }
+
+    /// Create an `AllocBlob` from a vector already in the allocator
+    #[inline]
+    pub fn from_vec(data: AllocVec<u8, A>) -> AllocBlob<C, A> {
+        AllocBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode an `AllocBlob` from the given string into the given allocator, as
+    /// `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64_in<T>(encoded: T, alloc: A) -> Result<AllocBlob<C, A>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        AllocBlob::decode_blocks_in(encoded.as_ref(), alloc, C::decode, Blob::decode_base64)
+    }
+
+    /// Decode an `AllocBlob` from the given string into the given allocator, skipping any ASCII
+    /// whitespace as `Blob::decode_base64_lenient` does
+    ///
+    /// Input with whitespace decodes into a temporary `Blob` first.
+    #[inline]
+    pub fn decode_base64_lenient_in<T>(encoded: T, alloc: A) -> Result<AllocBlob<C, A>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        AllocBlob::decode_blocks_in(encoded, alloc, C::decode, Blob::decode_base64_lenient)
+    }
+
+    /// Decode an `AllocBlob` from the given string into the given allocator, accepting input
+    /// with or without padding as `Blob::decode_base64_any_padding` does
+    #[inline]
+    pub fn decode_base64_any_padding_in<T>(encoded: T, alloc: A) -> Result<AllocBlob<C, A>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        AllocBlob::decode_blocks_in(
+            encoded,
+            alloc,
+            C::decode_any_padding,
+            Blob::decode_base64_any_padding,
+        )
+    }
+
+    /// Decode an `AllocBlob` from the given string into the given allocator, only accepting
+    /// the exact form that encoding produces, as `Blob::decode_base64_strict` does
+    ///
+    /// The canonical form can only be checked against the whole input, so this decodes into a
+    /// temporary `Blob` first.
+    #[inline]
+    pub fn decode_base64_strict_in<T>(encoded: T, alloc: A) -> Result<AllocBlob<C, A>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::<C>::decode_base64_strict(encoded).map(|blob| AllocBlob::from_slice_in(&blob, alloc))
+    }
+
+    /// Decode case-insensitive hexadecimal data into the given allocator, regardless of `C`
+    #[inline]
+    pub fn from_hex_in<T>(encoded: T, alloc: A) -> Result<AllocBlob<C, A>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        AllocBlob::<Hex, A>::decode_base64_in(encoded, alloc)
+            .map(|blob| AllocBlob::from_vec(blob.into_vec()))
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from a reader into the given allocator, ignoring
+    /// ASCII whitespace as `Blob::decode_from_reader` does
+    ///
+    /// Bytes are moved into the allocator as each read is decoded, while configs without an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN) read all
+    /// of the text and decode it into a temporary `Blob` first.
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader_in<R: io::Read>(reader: R, alloc: A) -> io::Result<AllocBlob<C, A>> {
+        let mut blob = AllocBlob::new_in(alloc);
+        let mut decoder = StreamDecoder::<C>::new();
+
+        read_into(reader, &mut decoder, |decoder| {
+            decoder.take_decoded(|bytes| blob.extend_from_slice(bytes))
+        })?;
+
+        blob.extend_from_slice(&decoder.finish().map_err(invalid_data)?);
+
+        Ok(blob)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from a vector already in the allocator, reusing its
+    /// allocation for the decoded bytes as `Blob::decode_owned` does
+    ///
+    /// Configs without an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN), and input
+    /// containing whitespace, decode into a temporary `Blob` first, which is then copied back.
+    pub fn decode_owned(mut encoded: AllocVec<u8, A>) -> Result<AllocBlob<C, A>, Error> {
+        match decode_front::<C>(&mut encoded) {
+            Some(written) => encoded.truncate(written?),
+            None => {
+                let blob = Blob::<C>::decode_base64(&encoded)?;
+
+                // decoded bytes are never longer than the input
+                encoded.clear();
+                encoded.extend_from_slice(&blob);
+            }
+        }
+
+        Ok(AllocBlob::from_vec(encoded))
+    }
+
+    /// Returns a `DeserializeSeed` that deserializes an `AllocBlob` into the given allocator,
+    /// as a `Blob<C>` would be deserialized
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn seed_in(alloc: A) -> AllocSeed<C, A> {
+        AllocSeed {
+            alloc,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decodes `encoded` into `alloc` a chunk at a time with `decode`, or copies the `Blob`
+    /// decoded by `fallback` for configs without an `ENCODED_BLOCK_LEN` and input with
+    /// whitespace
+    fn decode_blocks_in<'a, F>(
+        encoded: &'a [u8],
+        alloc: A,
+        decode: DecodeFn,
+        fallback: F,
+    ) -> Result<AllocBlob<C, A>, Error>
+    where
+        F: FnOnce(&'a [u8]) -> Result<Blob<C>, Error>,
+    {
+        let quantum = match C::ENCODED_BLOCK_LEN {
+            Some(quantum) if !encoded.iter().any(u8::is_ascii_whitespace) => quantum,
+            _ => return fallback(encoded).map(|blob| AllocBlob::from_slice_in(&blob, alloc)),
+        };
+
+        // nothing is allocated for input that could only decode to too many bytes
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        let max_len = match C::decoded_len_bounds(encoded.len()) {
+            (_, usize::MAX) => encoded.len(),
+            (_, max_len) => max_len,
+        };
+
+        let max_len = C::MAX_DECODED_LEN.map_or(max_len, |max| max_len.min(max));
+        let chunk_len = (CHUNK_LEN / quantum).max(1) * quantum;
+
+        let mut blob = AllocBlob::with_capacity_in(max_len, alloc);
+        let mut scratch = Vec::with_capacity(chunk_len);
+
+        for start in (0..encoded.len()).step_by(chunk_len) {
+            let end = encoded.len().min(start + chunk_len);
+
+            scratch.clear();
+
+            decode_chunk::<C>(encoded, start..end, &mut scratch, decode)?;
+
+            blob.data.extend_from_slice(&scratch);
+
+            if let Some(max) = C::MAX_DECODED_LEN {
+                if blob.len() > max {
+                    return Err(Error::too_long(max, any::type_name::<C>(), encoded.len()));
+                }
+            }
+        }
+
+        Ok(blob)
+    }
+
+    /// Encode the `AllocBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Returns the allocator the bytes are stored in
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.data.allocator()
+    }
+
+    /// Appends the given bytes
+    #[inline]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Copy the bytes into a `Blob` in the global allocator
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.data[..])
+    }
+
+    /// Consume self and return the inner vector
+    #[inline]
+    pub fn into_vec(self) -> AllocVec<u8, A> {
+        self.data
+    }
+}
+
+impl<C: Config, A: Allocator + Default> Default for AllocBlob<C, A> {
+    #[inline]
+    fn default() -> AllocBlob<C, A> {
+        AllocBlob::new_in(A::default())
+    }
+}
+
+/// Copies the bytes into the same allocator
+impl<C: Config, A: Allocator + Clone> Clone for AllocBlob<C, A> {
+    #[inline]
+    fn clone(&self) -> AllocBlob<C, A> {
+        AllocBlob::from_vec(self.data.clone())
+    }
+}
+
+impl<C: Config, A: Allocator> fmt::Debug for AllocBlob<C, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AllocBlob").field(&&self.data[..]).finish()
+    }
+}
+
+impl<C: Config, A: Allocator> Display for AllocBlob<C, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+impl<C: Config, A: Allocator, B: Allocator> PartialEq<AllocBlob<C, B>> for AllocBlob<C, A> {
+    #[inline(always)]
+    fn eq(&self, other: &AllocBlob<C, B>) -> bool {
+        self.data[..] == other.data[..]
+    }
+}
+
+impl<C: Config, A: Allocator> Eq for AllocBlob<C, A> {}
+
+impl<C: Config, A: Allocator> PartialEq<Blob<C>> for AllocBlob<C, A> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.data[..] == other[..]
+    }
+}
+
+impl<C: Config, A: Allocator> Hash for AllocBlob<C, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data[..].hash(state);
+    }
+}
+
+impl<C: Config, A: Allocator> Deref for AllocBlob<C, A> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config, A: Allocator> DerefMut for AllocBlob<C, A> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, A: Allocator> AsRef<[u8]> for AllocBlob<C, A> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config, A: Allocator> AsMut<[u8]> for AllocBlob<C, A> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, A: Allocator> Extend<u8> for AllocBlob<C, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.data.extend(iter);
+    }
+}
+
+impl<C: Config, A: Allocator> From<AllocVec<u8, A>> for AllocBlob<C, A> {
+    #[inline(always)]
+    fn from(data: AllocVec<u8, A>) -> AllocBlob<C, A> {
+        AllocBlob::from_vec(data)
+    }
+}
+
+impl<C: Config, A: Allocator> From<AllocBlob<C, A>> for Blob<C> {
+    #[inline]
+    fn from(blob: AllocBlob<C, A>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, A: Allocator> serde::Serialize for AllocBlob<C, A> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+/// Deserializes into the default allocator, as `AllocBlob::seed_in` does
+#[cfg(feature = "serde")]
+impl<'de, C: Config, A: Allocator + Default> serde::Deserialize<'de> for AllocBlob<C, A> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::de::DeserializeSeed::deserialize(AllocBlob::seed_in(A::default()), deserializer)
+    }
+}
+
+/// `DeserializeSeed` for an `AllocBlob` in a given allocator, returned by `AllocBlob::seed_in`
+///
+/// Strings, bytes and sequences are decoded straight into the allocator, while the Node.js
+/// `Buffer` and Extended JSON forms decode into a temporary `Blob` first.
+#[cfg(feature = "serde")]
+pub struct AllocSeed<C: Config, A: Allocator> {
+    alloc: A,
+    _config: PhantomData<fn() -> C>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config, A: Allocator> serde::de::DeserializeSeed<'de> for AllocSeed<C, A> {
+    type Value = AllocBlob<C, A>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_bytes(self)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config, A: Allocator> serde::de::Visitor<'de> for AllocSeed<C, A> {
+    type Value = AllocBlob<C, A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let (offset, trimmed) = whitespace::trim(value);
+
+        // offsets are reported relative to the untrimmed string, as with `Blob::from_str`
+        AllocBlob::decode_base64_in(trimmed, self.alloc)
+            .map_err(|err| E::custom(err.within(offset, value.len())))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(AllocBlob::from_slice_in(value, self.alloc))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        let max = C::MAX_DECODED_LEN.unwrap_or(usize::MAX);
+        let capacity = visitor.size_hint().unwrap_or(0).min(4096).min(max);
+
+        let mut blob = AllocBlob::with_capacity_in(capacity, self.alloc);
+
+        while let Some(SeqByte(byte)) = visitor.next_element()? {
+            check_raw_len::<C, V::Error>(blob.len() + 1)?;
+
+            blob.data.push(byte);
+        }
+
+        Ok(blob)
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        let blob = BlobVisitor::<C>::new(false).visit_map(visitor)?;
+
+        Ok(AllocBlob::from_slice_in(&blob, self.alloc))
+    }
+}
@@ -0,0 +1,209 @@
+//! OpenPGP ASCII armor, as described in RFC 4880 section 6.2
+
+use alloc_crate::string::String;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+
+use super::{Blob, Config, Error, Standard};
+
+/// Number of bytes encoded on each line of the body, giving 64 characters per line
+pub(crate) const LINE_BYTES: usize = 48;
+
+/// Error returned when decoding ASCII armor into a `Blob`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArmorError {
+    /// The base-64 body or checksum was invalid
+    Decode(Error),
+    /// The `BEGIN` or `END` line was missing, the labels did not match, or the checksum line
+    /// was not three bytes
+    Malformed,
+    /// The CRC-24 checksum did not match the data
+    Mismatch,
+}
+
+impl Display for ArmorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ArmorError::Decode(ref err) => err.fmt(f),
+            ArmorError::Malformed => f.write_str("Malformed armor"),
+            ArmorError::Mismatch => f.write_str("Armor checksum mismatch"),
+        }
+    }
+}
+
+impl StdError for ArmorError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            ArmorError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ArmorError {
+    #[inline]
+    fn from(err: Error) -> ArmorError {
+        ArmorError::Decode(err)
+    }
+}
+
+/// CRC-24 of `data`, as defined by RFC 4880 section 6.1
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc = 0xb704ce;
+
+    for &b in data {
+        crc ^= (b as u32) << 16;
+
+        for _ in 0..8 {
+            crc <<= 1;
+
+            if crc & 0x1000000 != 0 {
+                crc ^= 0x1864cfb;
+            }
+        }
+    }
+
+    crc & 0xffffff
+}
+
+/// Lines of `input` with surrounding whitespace trimmed, along with their offsets
+fn lines(input: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut start = 0;
+
+    input.split(|&c| c == b'\n').map(move |line| {
+        let offset = start + line.iter().take_while(|c| c.is_ascii_whitespace()).count();
+
+        start += line.len() + 1;
+
+        (offset, line.trim_ascii())
+    })
+}
+
+/// Appends the armored form of `data` to `output`
+pub(crate) fn encode(label: &str, data: &[u8], output: &mut String) {
+    output.reserve(2 * label.len() + 40 + data.len().div_ceil(LINE_BYTES) * 65);
+
+    output.push_str("-----BEGIN ");
+    output.push_str(label);
+    output.push_str("-----\n\n");
+
+    for line in data.chunks(LINE_BYTES) {
+        Standard::encode(line, output);
+        output.push('\n');
+    }
+
+    let crc = crc24(data);
+
+    output.push('=');
+    Standard::encode(&[(crc >> 16) as u8, (crc >> 8) as u8, crc as u8], output);
+    output.push_str("\n-----END ");
+    output.push_str(label);
+    output.push_str("-----\n");
+}
+
+/// A block of lines framed by `-----BEGIN label-----` and `-----END label-----`
+pub(crate) struct Frame<'a> {
+    /// Text between `BEGIN` and the dashes
+    pub label: &'a [u8],
+    /// Start and end offsets of the body lines, empty if there were none
+    pub body: (usize, usize),
+    /// Offset and text of the checksum line after the leading `=`, if there was one
+    pub checksum: Option<(usize, &'a [u8])>,
+    /// Offset just past the `END` line
+    pub end: usize,
+}
+
+/// Finds the first framed block in `input`
+///
+/// Returns `None` if there is no `BEGIN` line, and `Some(Err(()))` if the block is not closed
+/// by a matching `END` line. Header lines containing `:` before the body are skipped, as are
+/// blank lines.
+pub(crate) fn frame(input: &[u8]) -> Option<Result<Frame<'_>, ()>> {
+    let mut lines = lines(input);
+
+    let label = lines.find_map(|(_, line)| {
+        line.strip_prefix(b"-----BEGIN ")
+            .and_then(|rest| rest.strip_suffix(b"-----"))
+    })?;
+
+    let mut body = None;
+    let mut checksum = None;
+
+    for (offset, line) in lines {
+        if line.starts_with(b"-----") {
+            let footer = line
+                .strip_prefix(b"-----END ")
+                .and_then(|rest| rest.strip_suffix(b"-----"));
+
+            if footer != Some(label) {
+                return Some(Err(()));
+            }
+
+            return Some(Ok(Frame {
+                label,
+                body: body.unwrap_or((0, 0)),
+                checksum,
+                end: (offset + line.len()).min(input.len()),
+            }));
+        }
+
+        // base-64 never contains `:`, and only the checksum line starts with `=`
+        if line.is_empty() || (body.is_none() && line.contains(&b':')) {
+            continue;
+        } else if checksum.is_some() {
+            return Some(Err(()));
+        } else if line.starts_with(b"=") {
+            checksum = Some((offset + 1, &line[1..]));
+        } else {
+            let (start, _) = body.unwrap_or((offset, 0));
+
+            body = Some((start, offset + line.len()));
+        }
+    }
+
+    Some(Err(()))
+}
+
+/// Decodes the body lines of `frame` within `input`
+pub(crate) fn decode_body(input: &[u8], frame: &Frame) -> Result<Blob<Standard>, Error> {
+    let (start, end) = frame.body;
+
+    Blob::<Standard>::decode_base64_lenient(&input[start..end])
+        .map_err(|err| err.within(start, input.len()))
+}
+
+/// Decodes the label of `frame`, which must be UTF-8
+pub(crate) fn decode_label(frame: &Frame) -> Option<String> {
+    String::from_utf8(frame.label.to_vec()).ok()
+}
+
+/// Decodes the first armored block in `input`, returning its label and bytes
+///
+/// Any text before the `BEGIN` line or after the `END` line is ignored, as are armor headers
+/// such as `Version: ...`. The checksum is optional, but verified when present.
+pub(crate) fn decode(input: &[u8]) -> Result<(String, Blob<Standard>), ArmorError> {
+    let frame = match frame(input) {
+        Some(Ok(frame)) => frame,
+        _ => return Err(ArmorError::Malformed),
+    };
+
+    let label = decode_label(&frame).ok_or(ArmorError::Malformed)?;
+    let blob = decode_body(input, &frame)?;
+
+    if let Some((offset, checksum)) = frame.checksum {
+        let checksum = Blob::<Standard>::decode_base64(checksum)
+            .map_err(|err| err.within(offset, input.len()))?;
+
+        if checksum.len() != 3 {
+            return Err(ArmorError::Malformed);
+        }
+
+        let crc = crc24(&blob);
+
+        if checksum[..] != [(crc >> 16) as u8, (crc >> 8) as u8, crc as u8] {
+            return Err(ArmorError::Mismatch);
+        }
+    }
+
+    Ok((label, blob))
+}
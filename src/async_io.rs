@@ -0,0 +1,461 @@
+//! Asynchronous I/O over blobs
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use alloc_crate::boxed::Box;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use std::future::Future;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use std::io::{self, BufRead};
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use std::pin::Pin;
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use std::task::{Context, Poll};
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use super::stream::{invalid_data, StreamDecoder, READ_LEN};
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+use super::{Blob, Config, EncodeReader};
+
+#[cfg(feature = "futures")]
+pub use self::futures_impls::EncodeStream;
+#[cfg(feature = "futures-io")]
+pub(crate) use self::futures_io_impls::FuturesIo;
+#[cfg(feature = "tokio")]
+pub(crate) use self::tokio_impls::{read_file, write_file, TokioIo};
+
+/// Writer of either async runtime, polled through an adapter
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+pub(crate) trait AsyncSink: Unpin {
+    fn poll_write(&mut self, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>>;
+}
+
+/// Reader of either async runtime, polled through an adapter
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+pub(crate) trait AsyncSource: Unpin {
+    fn poll_read(&mut self, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+}
+
+/// Future writing the encoded form of a blob, as returned by `Blob::encode_to_async`
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+pub(crate) struct EncodeTo<'a, C: Config, W> {
+    reader: EncodeReader<'a, C>,
+    writer: W,
+}
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+impl<'a, C: Config, W: AsyncSink> EncodeTo<'a, C, W> {
+    #[inline]
+    pub(crate) fn new(blob: &'a Blob<C>, writer: W) -> EncodeTo<'a, C, W> {
+        EncodeTo {
+            reader: blob.encode_reader(),
+            writer,
+        }
+    }
+}
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+impl<'a, C: Config, W: AsyncSink> Future for EncodeTo<'a, C, W> {
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        loop {
+            let n = {
+                let pending = this.reader.fill_buf()?;
+
+                if pending.is_empty() {
+                    return Poll::Ready(Ok(()));
+                }
+
+                match this.writer.poll_write(cx, pending) {
+                    Poll::Ready(Ok(0)) => {
+                        let err = io::Error::new(io::ErrorKind::WriteZero, "failed to write blob");
+
+                        return Poll::Ready(Err(err));
+                    }
+                    Poll::Ready(Ok(n)) => n,
+                    Poll::Ready(Err(ref err)) if err.kind() == io::ErrorKind::Interrupted => 0,
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            };
+
+            this.reader.consume(n);
+        }
+    }
+}
+
+/// Future decoding a blob from a reader, as returned by `Blob::decode_from_async`
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+pub(crate) struct DecodeFrom<C: Config, R> {
+    decoder: Option<StreamDecoder<C>>,
+    reader: R,
+    buf: Box<[u8]>,
+}
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+impl<C: Config, R: AsyncSource> DecodeFrom<C, R> {
+    #[inline]
+    pub(crate) fn new(reader: R) -> DecodeFrom<C, R> {
+        DecodeFrom {
+            decoder: Some(StreamDecoder::new()),
+            reader,
+            buf: vec![0; READ_LEN].into_boxed_slice(),
+        }
+    }
+}
+
+#[cfg(any(feature = "futures-io", feature = "tokio"))]
+impl<C: Config, R: AsyncSource> Future for DecodeFrom<C, R> {
+    type Output = io::Result<Blob<C>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<Blob<C>>> {
+        let this = self.get_mut();
+
+        loop {
+            let n = match this.reader.poll_read(cx, &mut this.buf) {
+                Poll::Ready(Ok(n)) => n,
+                Poll::Ready(Err(ref err)) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let decoder = this
+                .decoder
+                .as_mut()
+                .expect("`DecodeFrom` polled after completion");
+
+            if n == 0 {
+                let decoder = this.decoder.take().unwrap();
+
+                return Poll::Ready(decoder.finish().map_err(invalid_data));
+            }
+
+            decoder.write(&this.buf[..n]).map_err(invalid_data)?;
+        }
+    }
+}
+
+#[cfg(feature = "futures")]
+mod futures_impls {
+    use std::fmt;
+    use std::io::BufRead;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use bytes::{Bytes, BytesMut};
+    use futures_core::Stream;
+
+    use super::super::{Blob, Config, EncodeReader, Standard};
+
+    /// `Stream` of the encoded form of a `Blob`, as returned by [`Blob::encode_stream`]
+    ///
+    /// Every chunk but the last is exactly the chunk size, and the text is encoded lazily as
+    /// with `EncodeReader`, so a large blob can be served as a streaming body.
+    ///
+    /// [`Blob::encode_stream`]: struct.Blob.html#method.encode_stream
+    pub struct EncodeStream<'a, C: Config = Standard> {
+        reader: EncodeReader<'a, C>,
+        chunk_size: usize,
+    }
+
+    impl<'a, C: Config> EncodeStream<'a, C> {
+        /// Create a stream over the encoded form of the given `Blob`
+        ///
+        /// # Panics
+        ///
+        /// Panics if `chunk_size` is zero.
+        pub fn new(blob: &'a Blob<C>, chunk_size: usize) -> EncodeStream<'a, C> {
+            assert!(chunk_size > 0, "EncodeStream chunk size must not be zero");
+
+            EncodeStream {
+                reader: blob.encode_reader(),
+                chunk_size,
+            }
+        }
+
+        /// Returns the next chunk of encoded text, if any is left
+        fn next_chunk(&mut self) -> Option<Bytes> {
+            let mut chunk = BytesMut::new();
+
+            while chunk.len() < self.chunk_size {
+                // encoding into memory never fails
+                let pending = self.reader.fill_buf().unwrap_or(&[]);
+
+                if pending.is_empty() {
+                    break;
+                }
+
+                let n = pending.len().min(self.chunk_size - chunk.len());
+
+                chunk.extend_from_slice(&pending[..n]);
+                self.reader.consume(n);
+            }
+
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk.freeze())
+            }
+        }
+    }
+
+    impl<'a, C: Config> fmt::Debug for EncodeStream<'a, C> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.debug_struct("EncodeStream")
+                .field("reader", &self.reader)
+                .field("chunk_size", &self.chunk_size)
+                .finish()
+        }
+    }
+
+    /// Never pending, as the bytes are already in memory
+    impl<'a, C: Config> Stream for EncodeStream<'a, C> {
+        type Item = Bytes;
+
+        #[inline]
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<Bytes>> {
+            Poll::Ready(self.get_mut().next_chunk())
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impls {
+    use alloc_crate::boxed::Box;
+    use alloc_crate::vec::Vec;
+    use std::future::Future;
+    use std::io;
+    use std::panic;
+    use std::path::PathBuf;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, ReadBuf};
+    use tokio::task::{self, JoinHandle};
+
+    use super::super::file;
+    use super::super::{Blob, BlobReader, Config};
+    use super::{AsyncSink, AsyncSource};
+
+    /// Blocking file operation, as run by a `FileTask`
+    type FileOp<T> = Box<dyn FnOnce() -> io::Result<T> + Send>;
+
+    /// Future of a file operation on tokio's blocking thread pool, as `tokio::fs` runs them
+    ///
+    /// The operation is only spawned when first polled, so the future may be created outside
+    /// of a runtime.
+    pub(crate) struct FileTask<T, U> {
+        op: Option<FileOp<T>>,
+        task: Option<JoinHandle<io::Result<T>>>,
+        map: fn(T) -> U,
+    }
+
+    impl<T: Send + 'static, U> Future for FileTask<T, U> {
+        type Output = io::Result<U>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<U>> {
+            let this = self.get_mut();
+
+            if let Some(op) = this.op.take() {
+                this.task = Some(task::spawn_blocking(op));
+            }
+
+            let task = this
+                .task
+                .as_mut()
+                .expect("`FileTask` polled after completion");
+
+            match Pin::new(task).poll(cx) {
+                Poll::Ready(Ok(result)) => Poll::Ready(result.map(this.map)),
+                Poll::Ready(Err(err)) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+                Poll::Ready(Err(_)) => {
+                    let err = io::Error::new(io::ErrorKind::Interrupted, "file task was cancelled");
+
+                    Poll::Ready(Err(err))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    /// Reads a file as `file::read` does, on the blocking thread pool
+    pub(crate) fn read_file<U>(
+        path: PathBuf,
+        max: Option<usize>,
+        config: &'static str,
+        map: fn(Vec<u8>) -> U,
+    ) -> FileTask<Vec<u8>, U> {
+        FileTask {
+            op: Some(Box::new(move || file::read(&path, max, config))),
+            task: None,
+            map,
+        }
+    }
+
+    /// Writes a file as `file::write` does, on the blocking thread pool
+    pub(crate) fn write_file(path: PathBuf, data: Vec<u8>, atomic: bool) -> FileTask<(), ()> {
+        FileTask {
+            op: Some(Box::new(move || file::write(&path, &data, atomic))),
+            task: None,
+            map: |()| (),
+        }
+    }
+
+    /// Adapts a tokio reader or writer for `EncodeTo` and `DecodeFrom`
+    pub(crate) struct TokioIo<T>(pub T);
+
+    impl<W: AsyncWrite + Unpin> AsyncSink for TokioIo<W> {
+        #[inline]
+        fn poll_write(&mut self, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+    }
+
+    impl<R: AsyncRead + Unpin> AsyncSource for TokioIo<R> {
+        fn poll_read(&mut self, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            let mut buf = ReadBuf::new(buf);
+
+            match Pin::new(&mut self.0).poll_read(cx, &mut buf) {
+                Poll::Ready(Ok(())) => Poll::Ready(Ok(buf.filled().len())),
+                Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+
+    /// Never pending, as the bytes are already in memory
+    impl<'a, C: Config> AsyncRead for BlobReader<'a, C> {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &mut ReadBuf,
+        ) -> Poll<io::Result<()>> {
+            let this = self.get_mut();
+            let remaining = this.remaining();
+            let n = remaining.len().min(buf.remaining());
+
+            buf.put_slice(&remaining[..n]);
+
+            let pos = this.position() + n as u64;
+
+            this.set_position(pos);
+
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<'a, C: Config> AsyncBufRead for BlobReader<'a, C> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<&[u8]>> {
+            Poll::Ready(Ok(self.get_mut().remaining()))
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            io::BufRead::consume(self.get_mut(), amt)
+        }
+    }
+
+    /// Appends the bytes written, as the `io::Write` implementation does
+    impl<C: Config> AsyncWrite for Blob<C> {
+        #[inline]
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.get_mut().data.extend_from_slice(buf);
+
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        #[inline]
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+#[cfg(feature = "futures-io")]
+mod futures_io_impls {
+    use std::io;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite};
+
+    use super::super::{Blob, BlobReader, Config};
+    use super::{AsyncSink, AsyncSource};
+
+    /// Adapts a `futures-io` reader or writer for `EncodeTo` and `DecodeFrom`
+    pub(crate) struct FuturesIo<T>(pub T);
+
+    impl<W: AsyncWrite + Unpin> AsyncSink for FuturesIo<W> {
+        #[inline]
+        fn poll_write(&mut self, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.0).poll_write(cx, buf)
+        }
+    }
+
+    impl<R: AsyncRead + Unpin> AsyncSource for FuturesIo<R> {
+        #[inline]
+        fn poll_read(&mut self, cx: &mut Context, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    /// Never pending, as the bytes are already in memory
+    impl<'a, C: Config> AsyncRead for BlobReader<'a, C> {
+        #[inline]
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(io::Read::read(self.get_mut(), buf))
+        }
+    }
+
+    impl<'a, C: Config> AsyncBufRead for BlobReader<'a, C> {
+        #[inline]
+        fn poll_fill_buf(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<&[u8]>> {
+            Poll::Ready(Ok(self.get_mut().remaining()))
+        }
+
+        #[inline]
+        fn consume(self: Pin<&mut Self>, amt: usize) {
+            io::BufRead::consume(self.get_mut(), amt)
+        }
+    }
+
+    /// Appends the bytes written, as the `io::Write` implementation does
+    impl<C: Config> AsyncWrite for Blob<C> {
+        #[inline]
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.get_mut().data.extend_from_slice(buf);
+
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
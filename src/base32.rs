@@ -0,0 +1,348 @@
+//! Base-32 encoding and decoding, as per RFC 4648
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use base64::DecodeError;
+
+/// Marker for bytes outside of the alphabet in a decode table
+pub const INVALID: u8 = 0xff;
+
+/// A base-32 alphabet, with its decode table
+pub struct Alphabet {
+    pub encode: &'static [u8; 32],
+    pub decode: [u8; 256],
+}
+
+/// Builds a decode table for the given alphabet, accepting lowercase letters as well
+pub const fn decode_table(encode: &[u8; 32]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < 32 {
+        let c = encode[i];
+
+        table[c as usize] = i as u8;
+        table[c.to_ascii_lowercase() as usize] = i as u8;
+
+        i += 1;
+    }
+
+    table
+}
+
+/// RFC 4648 standard alphabet
+pub static STANDARD: Alphabet = Alphabet {
+    encode: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+    decode: decode_table(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+};
+
+/// RFC 4648 "Extended Hex" alphabet
+pub static HEX: Alphabet = Alphabet {
+    encode: b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+    decode: decode_table(b"0123456789ABCDEFGHIJKLMNOPQRSTUV"),
+};
+
+/// Crockford's base-32 alphabet
+pub static CROCKFORD: Alphabet = Alphabet {
+    encode: b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+    decode: {
+        let mut table = decode_table(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+
+        table[b'O' as usize] = 0;
+        table[b'o' as usize] = 0;
+        table[b'I' as usize] = 1;
+        table[b'i' as usize] = 1;
+        table[b'L' as usize] = 1;
+        table[b'l' as usize] = 1;
+
+        table
+    },
+};
+
+/// Crockford's check symbols, for values `0..37`
+const CROCKFORD_CHECK: &[u8; 37] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+/// Returns the Crockford check symbol value, computed over the value of the given symbols
+fn crockford_check_value(symbols: &[u8]) -> u8 {
+    symbols.iter().fold(0u32, |check, &c| {
+        (check * 32 + CROCKFORD.decode[c as usize] as u32) % 37
+    }) as u8
+}
+
+/// Appends the Crockford base-32 encoding of `input` to `output`, optionally with a check symbol
+pub fn encode_crockford(input: &[u8], check: bool, output: &mut String) {
+    let start = output.len();
+
+    encode(input, &CROCKFORD, false, output);
+
+    if check {
+        let value = crockford_check_value(&output.as_bytes()[start..]);
+
+        output.push(CROCKFORD_CHECK[value as usize] as char);
+    }
+}
+
+/// Decodes Crockford base-32 from `input`, appending the bytes to `output`
+///
+/// Hyphens are ignored, and `O`, `I` and `L` are read as their look-alike digits.
+/// If `check` is true, the last symbol must be a valid check symbol.
+pub fn decode_crockford(
+    input: &[u8],
+    check: bool,
+    output: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    let mut symbols: Vec<u8> = Vec::with_capacity(input.len());
+    let mut offsets: Vec<usize> = Vec::with_capacity(input.len());
+
+    for (i, &c) in input.iter().enumerate() {
+        if c != b'-' {
+            symbols.push(c);
+            offsets.push(i);
+        }
+    }
+
+    if check {
+        let c = symbols.pop().ok_or(DecodeError::InvalidLength(0))?;
+        let offset = offsets.pop().unwrap_or(0);
+
+        let expected = match CROCKFORD_CHECK
+            .iter()
+            .position(|&s| s == c.to_ascii_uppercase())
+        {
+            Some(expected) => expected,
+            None if CROCKFORD.decode[c as usize] != INVALID => {
+                CROCKFORD.decode[c as usize] as usize
+            }
+            None => return Err(DecodeError::InvalidByte(offset, c)),
+        };
+
+        if let Some(i) = symbols
+            .iter()
+            .position(|&c| CROCKFORD.decode[c as usize] == INVALID)
+        {
+            return Err(DecodeError::InvalidByte(offsets[i], symbols[i]));
+        }
+
+        if crockford_check_value(&symbols) as usize != expected {
+            return Err(DecodeError::InvalidLastSymbol(offset, c));
+        }
+    }
+
+    decode(&symbols, &CROCKFORD, Padding::Forbidden, output).map_err(|err| match err {
+        DecodeError::InvalidByte(i, c) => DecodeError::InvalidByte(offsets[i], c),
+        DecodeError::InvalidLastSymbol(i, c) => DecodeError::InvalidLastSymbol(offsets[i], c),
+        DecodeError::InvalidLength(len) => DecodeError::InvalidLength(len),
+        DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+    })
+}
+
+/// Validates Crockford base-32 from `input` as `decode_crockford` would, returning the decoded
+/// length, without copying the symbols
+pub fn validate_crockford(input: &[u8], check: bool) -> Result<usize, DecodeError> {
+    let symbols = input.iter().enumerate().filter(|&(_, &c)| c != b'-');
+    let count = symbols.clone().count();
+
+    let data_len = if check {
+        count.checked_sub(1).ok_or(DecodeError::InvalidLength(0))?
+    } else {
+        count
+    };
+
+    let invalid_length = match data_len % 8 {
+        0 | 2 | 4 | 5 | 7 => None,
+        _ => Some(DecodeError::InvalidLength(data_len)),
+    };
+
+    let expected = match symbols.clone().nth(data_len) {
+        Some((offset, &c)) if check => {
+            let expected = match CROCKFORD_CHECK
+                .iter()
+                .position(|&s| s == c.to_ascii_uppercase())
+            {
+                Some(expected) => expected,
+                None if CROCKFORD.decode[c as usize] != INVALID => {
+                    CROCKFORD.decode[c as usize] as usize
+                }
+                None => return Err(DecodeError::InvalidByte(offset, c)),
+            };
+
+            Some((offset, c, expected))
+        }
+        _ => None,
+    };
+
+    // without a check symbol, the length is checked before the symbols
+    if let (None, Some(err)) = (expected, invalid_length.clone()) {
+        return Err(err);
+    }
+
+    let mut value = 0u32;
+    let mut last = None;
+
+    for (i, &c) in symbols.take(data_len) {
+        let symbol = CROCKFORD.decode[c as usize];
+
+        if symbol == INVALID {
+            return Err(DecodeError::InvalidByte(i, c));
+        }
+
+        value = (value * 32 + symbol as u32) % 37;
+        last = Some((i, c, symbol));
+    }
+
+    if let Some((offset, c, expected)) = expected {
+        if value as usize != expected {
+            return Err(DecodeError::InvalidLastSymbol(offset, c));
+        }
+
+        if let Some(err) = invalid_length {
+            return Err(err);
+        }
+    }
+
+    let remainder = data_len % 8;
+    let trailing_bits = remainder * 5 - remainder * 5 / 8 * 8;
+
+    match last {
+        Some((i, c, symbol)) if symbol & ((1 << trailing_bits) - 1) != 0 => {
+            Err(DecodeError::InvalidLastSymbol(i, c))
+        }
+        _ => Ok(data_len / 8 * 5 + remainder * 5 / 8),
+    }
+}
+
+/// Returns the length of the base-32 encoding of `len` bytes
+#[inline]
+pub fn encoded_len(len: usize, pad: bool) -> usize {
+    if pad {
+        len.div_ceil(5) * 8
+    } else {
+        len / 5 * 8 + (len % 5 * 8).div_ceil(5)
+    }
+}
+
+/// Appends the base-32 encoding of `input` to `output`
+pub fn encode(input: &[u8], alphabet: &Alphabet, pad: bool, output: &mut String) {
+    output.reserve(encoded_len(input.len(), pad));
+
+    for chunk in input.chunks(5) {
+        let mut buf = [0u8; 8];
+        buf[3..3 + chunk.len()].copy_from_slice(chunk);
+
+        let n = u64::from_be_bytes(buf);
+        let symbols = (chunk.len() * 8).div_ceil(5);
+
+        for i in 0..symbols {
+            output.push(alphabet.encode[((n >> (35 - i * 5)) & 0x1f) as usize] as char);
+        }
+
+        if pad {
+            for _ in symbols..8 {
+                output.push('=');
+            }
+        }
+    }
+}
+
+/// How padding is treated when decoding
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    Required,
+    Forbidden,
+    Indifferent,
+}
+
+impl Padding {
+    #[inline]
+    pub const fn from_pad(pad: bool) -> Padding {
+        if pad {
+            Padding::Required
+        } else {
+            Padding::Forbidden
+        }
+    }
+}
+
+/// Decodes base-32 from `input`, appending the bytes to `output`
+///
+/// Padding, if present, must be of the correct length, and non-zero trailing bits are rejected.
+pub fn decode(
+    input: &[u8],
+    alphabet: &Alphabet,
+    padding: Padding,
+    output: &mut Vec<u8>,
+) -> Result<(), DecodeError> {
+    output.reserve(input.len() * 5 / 8);
+
+    decode_with(input, alphabet, padding, |bytes| {
+        output.extend_from_slice(bytes)
+    })
+}
+
+/// Validates base-32 from `input` as `decode` would, returning the decoded length
+pub fn validate(input: &[u8], alphabet: &Alphabet, padding: Padding) -> Result<usize, DecodeError> {
+    let mut len = 0;
+
+    decode_with(input, alphabet, padding, |bytes| len += bytes.len())?;
+
+    Ok(len)
+}
+
+/// Decodes base-32 from `input` as `decode` does, passing each decoded group to `emit`
+fn decode_with<F>(
+    input: &[u8],
+    alphabet: &Alphabet,
+    padding: Padding,
+    mut emit: F,
+) -> Result<(), DecodeError>
+where
+    F: FnMut(&[u8]),
+{
+    let mut data_len = input.len();
+
+    if padding != Padding::Forbidden {
+        while data_len > 0 && input[data_len - 1] == b'=' {
+            data_len -= 1;
+        }
+    }
+
+    let remainder = data_len % 8;
+
+    match remainder {
+        0 | 2 | 4 | 5 | 7 => {}
+        _ => return Err(DecodeError::InvalidLength(data_len)),
+    }
+
+    let pad_len = input.len() - data_len;
+
+    if (padding == Padding::Required || pad_len > 0) && pad_len != (8 - remainder) % 8 {
+        return Err(DecodeError::InvalidPadding);
+    }
+
+    for (i, chunk) in input[..data_len].chunks(8).enumerate() {
+        let mut n = 0u64;
+
+        for (j, &c) in chunk.iter().enumerate() {
+            let value = alphabet.decode[c as usize];
+
+            if value == INVALID {
+                return Err(DecodeError::InvalidByte(i * 8 + j, c));
+            }
+
+            n |= (value as u64) << (35 - j * 5);
+        }
+
+        let bytes = chunk.len() * 5 / 8;
+
+        if n & ((1u64 << (40 - bytes * 8)) - 1) != 0 {
+            let last = chunk.len() - 1;
+
+            return Err(DecodeError::InvalidLastSymbol(i * 8 + last, chunk[last]));
+        }
+
+        emit(&n.to_be_bytes()[3..3 + bytes]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Base-58 encoding and decoding, using the Bitcoin alphabet
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use base64::DecodeError;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+const INVALID: u8 = 0xff;
+
+static DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < 58 {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+/// Appends the base-58 encoding of `input` to `output`
+pub fn encode(input: &[u8], output: &mut String) {
+    let zeroes = input.iter().take_while(|&&b| b == 0).count();
+
+    // little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity((input.len() - zeroes) * 138 / 100 + 1);
+
+    for &byte in &input[zeroes..] {
+        let mut carry = byte as u32;
+
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    output.reserve(zeroes + digits.len());
+
+    for _ in 0..zeroes {
+        output.push('1');
+    }
+
+    for &digit in digits.iter().rev() {
+        output.push(ALPHABET[digit as usize] as char);
+    }
+}
+
+/// Decodes base-58 from `input`, appending the bytes to `output`
+pub fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+    let ones = input.iter().take_while(|&&c| c == b'1').count();
+
+    // little-endian base-256 bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity((input.len() - ones) * 733 / 1000 + 1);
+
+    for (i, &c) in input.iter().enumerate().skip(ones) {
+        let value = DECODE[c as usize];
+
+        if value == INVALID {
+            return Err(DecodeError::InvalidByte(i, c));
+        }
+
+        let mut carry = value as u32;
+
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    output.reserve(ones + bytes.len());
+    output.resize(output.len() + ones, 0);
+    output.extend(bytes.iter().rev());
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! Base-85 encoding and decoding, in the Ascii85 and Z85 flavors
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use base64::DecodeError;
+
+const INVALID: u8 = 0xff;
+
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+static Z85_DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < 85 {
+        table[Z85_ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+};
+
+#[inline]
+fn encode_word(mut word: u32) -> [u8; 5] {
+    let mut digits = [0u8; 5];
+
+    for digit in digits.iter_mut().rev() {
+        *digit = (word % 85) as u8;
+        word /= 85;
+    }
+
+    digits
+}
+
+/// Appends the Ascii85 encoding of `input` to `output`, without `<~ ~>` delimiters
+///
+/// All-zero groups are abbreviated as `z`, and a trailing partial group is
+/// emitted as `n + 1` characters.
+pub fn encode_ascii85(input: &[u8], output: &mut String) {
+    output.reserve(input.len().div_ceil(4) * 5);
+
+    for chunk in input.chunks(4) {
+        let mut buf = [0u8; 4];
+        buf[..chunk.len()].copy_from_slice(chunk);
+
+        let word = u32::from_be_bytes(buf);
+
+        if word == 0 && chunk.len() == 4 {
+            output.push('z');
+            continue;
+        }
+
+        for &digit in &encode_word(word)[..chunk.len() + 1] {
+            output.push((digit + b'!') as char);
+        }
+    }
+}
+
+/// Decodes Ascii85 from `input`, appending the bytes to `output`
+///
+/// Surrounding `<~ ~>` delimiters and ASCII whitespace are ignored, while a final partial group
+/// must be exactly as `encode_ascii85` writes it.
+pub fn decode_ascii85(input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+    output.reserve(input.len() / 5 * 4);
+
+    decode_ascii85_with(input, |bytes| output.extend_from_slice(bytes))
+}
+
+/// Validates Ascii85 from `input` as `decode_ascii85` would, returning the decoded length
+pub fn validate_ascii85(input: &[u8]) -> Result<usize, DecodeError> {
+    let mut len = 0;
+
+    decode_ascii85_with(input, |bytes| len += bytes.len())?;
+
+    Ok(len)
+}
+
+/// Decodes Ascii85 from `input` as `decode_ascii85` does, passing each decoded group to `emit`
+fn decode_ascii85_with<F>(input: &[u8], mut emit: F) -> Result<(), DecodeError>
+where
+    F: FnMut(&[u8]),
+{
+    let mut start = 0;
+    let mut end = input.len();
+
+    if input.starts_with(b"<~") {
+        start = 2;
+    }
+
+    if input[start..].ends_with(b"~>") {
+        end -= 2;
+    }
+
+    let mut group = [0u8; 5];
+    let mut count = 0;
+    let mut last = 0;
+
+    for (i, &c) in input.iter().enumerate().take(end).skip(start) {
+        match c {
+            b'z' if count == 0 => emit(&[0; 4]),
+            b'!'..=b'u' => {
+                group[count] = c - b'!';
+                count += 1;
+                last = i;
+
+                if count == 5 {
+                    let word = decode_word(&group).ok_or(DecodeError::InvalidByte(i, c))?;
+
+                    emit(&word.to_be_bytes());
+                    count = 0;
+                }
+            }
+            _ if c.is_ascii_whitespace() => {}
+            _ => return Err(DecodeError::InvalidByte(i, c)),
+        }
+    }
+
+    match count {
+        0 => {}
+        1 => return Err(DecodeError::InvalidLength(end - start)),
+        _ => {
+            // pad the partial group with the highest digit, then drop the extra bytes
+            for digit in group.iter_mut().skip(count) {
+                *digit = 84;
+            }
+
+            let word = decode_word(&group).ok_or(DecodeError::InvalidLength(end - start))?;
+            let bytes = word.to_be_bytes();
+
+            // only the digits `encode_ascii85` would produce for these bytes are accepted
+            let mut buf = [0u8; 4];
+            buf[..count - 1].copy_from_slice(&bytes[..count - 1]);
+
+            if encode_word(u32::from_be_bytes(buf))[..count] != group[..count] {
+                return Err(DecodeError::InvalidLastSymbol(last, input[last]));
+            }
+
+            emit(&bytes[..count - 1]);
+        }
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn decode_word(digits: &[u8; 5]) -> Option<u32> {
+    digits.iter().try_fold(0u32, |word, &digit| {
+        word.checked_mul(85)?.checked_add(digit as u32)
+    })
+}
+
+/// Appends the Z85 encoding of `input` to `output`
+///
+/// Z85 requires the input length to be a multiple of 4, so `None` is returned otherwise.
+pub fn encode_z85(input: &[u8], output: &mut String) -> Option<()> {
+    if !input.len().is_multiple_of(4) {
+        return None;
+    }
+
+    output.reserve(input.len() / 4 * 5);
+
+    for chunk in input.chunks(4) {
+        let word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+
+        for &digit in &encode_word(word) {
+            output.push(Z85_ALPHABET[digit as usize] as char);
+        }
+    }
+
+    Some(())
+}
+
+/// Decodes Z85 from `input`, appending the bytes to `output`
+///
+/// The input length must be a multiple of 5.
+pub fn decode_z85(input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+    if !input.len().is_multiple_of(5) {
+        return Err(DecodeError::InvalidLength(input.len()));
+    }
+
+    output.reserve(input.len() / 5 * 4);
+
+    for (i, chunk) in input.chunks(5).enumerate() {
+        let mut group = [0u8; 5];
+
+        for (j, (digit, &c)) in group.iter_mut().zip(chunk).enumerate() {
+            *digit = Z85_DECODE[c as usize];
+
+            if *digit == INVALID {
+                return Err(DecodeError::InvalidByte(i * 5 + j, c));
+            }
+        }
+
+        let word = decode_word(&group).ok_or(DecodeError::InvalidByte(i * 5 + 4, chunk[4]))?;
+
+        output.extend_from_slice(&word.to_be_bytes());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Blobs backed by `bytes::Bytes`, for zero-copy interplay with network buffers
+
+use alloc_crate::string::String;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+use bytes::{Bytes, BytesMut};
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Error, Standard};
+
+/// Blob structure storing its binary data in `bytes::Bytes`
+///
+/// Request and response bodies from hyper, tonic and similar crates can be wrapped without
+/// copying, and cloning only increments a reference count. It encodes, displays and serializes
+/// exactly as a `Blob<C>`.
+pub struct BytesBlob<C: Config = Standard> {
+    data: Bytes,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> BytesBlob<C> {
+    /// Create a `BytesBlob` from the given `Bytes`
+    #[inline]
+    pub fn from_bytes(data: Bytes) -> BytesBlob<C> {
+        BytesBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode a `BytesBlob` from the given string, as `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<BytesBlob<C>, Error> {
+        Blob::decode_base64(encoded).map(BytesBlob::from)
+    }
+
+    /// Encode the `BytesBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.data[..])
+    }
+
+    /// Consume self and return the inner `Bytes`
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.data
+    }
+}
+
+/// Clones the reference to the bytes, not the bytes themselves
+impl<C: Config> Clone for BytesBlob<C> {
+    #[inline]
+    fn clone(&self) -> BytesBlob<C> {
+        BytesBlob::from_bytes(self.data.clone())
+    }
+}
+
+impl<C: Config> Default for BytesBlob<C> {
+    #[inline]
+    fn default() -> BytesBlob<C> {
+        BytesBlob::from_bytes(Bytes::new())
+    }
+}
+
+impl<C: Config> fmt::Debug for BytesBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BytesBlob").field(&&*self.data).finish()
+    }
+}
+
+impl<C: Config> Display for BytesBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `BytesBlob` from a string, as `Blob` would
+impl<C: Config> FromStr for BytesBlob<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(BytesBlob::from)
+    }
+}
+
+impl<C: Config> PartialEq for BytesBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config> Eq for BytesBlob<C> {}
+
+impl<C: Config> PartialEq<Blob<C>> for BytesBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        *self.data == other[..]
+    }
+}
+
+impl<C: Config> Hash for BytesBlob<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl<C: Config> Deref for BytesBlob<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for BytesBlob<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> From<Bytes> for BytesBlob<C> {
+    #[inline(always)]
+    fn from(data: Bytes) -> BytesBlob<C> {
+        BytesBlob::from_bytes(data)
+    }
+}
+
+impl<C: Config> From<BytesMut> for BytesBlob<C> {
+    #[inline]
+    fn from(data: BytesMut) -> BytesBlob<C> {
+        BytesBlob::from_bytes(data.freeze())
+    }
+}
+
+/// Takes ownership of the `Vec<u8>` without copying
+impl<C: Config> From<Blob<C>> for BytesBlob<C> {
+    #[inline]
+    fn from(blob: Blob<C>) -> BytesBlob<C> {
+        BytesBlob::from_bytes(blob.into_vec().into())
+    }
+}
+
+impl<'a, C: Config> From<&'a [u8]> for BytesBlob<C> {
+    #[inline]
+    fn from(data: &'a [u8]) -> BytesBlob<C> {
+        BytesBlob::from_bytes(Bytes::copy_from_slice(data))
+    }
+}
+
+impl<C: Config> From<BytesBlob<C>> for Bytes {
+    #[inline(always)]
+    fn from(blob: BytesBlob<C>) -> Bytes {
+        blob.data
+    }
+}
+
+impl<C: Config> From<BytesBlob<C>> for Blob<C> {
+    #[inline]
+    fn from(blob: BytesBlob<C>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for BytesBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for BytesBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(BytesBlob::from)
+    }
+}
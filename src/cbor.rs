@@ -0,0 +1,224 @@
+//! Blobs annotated with CBOR expected-encoding tags
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+
+use serde_crate as serde;
+use serde_crate::de::{Error, Unexpected};
+use serde_crate::ser::SerializeTupleVariant;
+
+use super::{Blob, Config, Standard};
+
+// the enum through which ciborium serializes and deserializes tags
+const TAG_ENUM: &str = "@@TAG@@";
+const UNTAGGED: &str = "@@UNTAGGED@@";
+const TAGGED: &str = "@@TAGGED@@";
+
+/// CBOR tag for a byte string expected to be encoded as URL-safe base-64
+const TAG_BASE64URL: u64 = 21;
+/// CBOR tag for a byte string expected to be encoded as standard base-64
+const TAG_BASE64: u64 = 22;
+/// CBOR tag for a byte string expected to be encoded as hexadecimal
+const TAG_BASE16: u64 = 23;
+
+/// Wrapper around `Blob` annotating its byte string with a CBOR expected-encoding tag
+///
+/// For formats that are not human-readable, the raw bytes are serialized with tag 21, 22 or
+/// 23 when the `CONTENT_ENCODING` of `C` is `"base64url"`, `"base64"` or `"base16"`
+/// respectively, and untagged otherwise. Deserializing accepts a byte string with any of
+/// those tags, or none. Human-readable formats serialize and deserialize exactly as `Blob`.
+///
+/// Tags are expressed the way [ciborium](https://docs.rs/ciborium) expects. Other binary
+/// formats round-trip the tag as an ordinary enum variant.
+pub struct Tagged<C: Config = Standard>(pub Blob<C>);
+
+impl<C: Config> Tagged<C> {
+    /// Returns the tag of the serialized byte string, if `C` has one
+    pub fn tag() -> Option<u64> {
+        match C::CONTENT_ENCODING {
+            Some("base64url") => Some(TAG_BASE64URL),
+            Some("base64") => Some(TAG_BASE64),
+            Some("base16") => Some(TAG_BASE16),
+            _ => None,
+        }
+    }
+
+    /// Consume self and return the inner `Blob`
+    #[inline]
+    pub fn into_inner(self) -> Blob<C> {
+        self.0
+    }
+}
+
+impl<C: Config> Clone for Tagged<C> {
+    #[inline]
+    fn clone(&self) -> Tagged<C> {
+        Tagged(self.0.clone())
+    }
+}
+
+impl<C: Config> fmt::Debug for Tagged<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Tagged").field(&&*self.0).finish()
+    }
+}
+
+impl<C: Config> PartialEq for Tagged<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: Config> Eq for Tagged<C> {}
+
+impl<C: Config> From<Blob<C>> for Tagged<C> {
+    #[inline(always)]
+    fn from(blob: Blob<C>) -> Tagged<C> {
+        Tagged(blob)
+    }
+}
+
+impl<C: Config> Deref for Tagged<C> {
+    type Target = Blob<C>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blob<C> {
+        &self.0
+    }
+}
+
+impl<C: Config> DerefMut for Tagged<C> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Blob<C> {
+        &mut self.0
+    }
+}
+
+impl<C: Config> serde::Serialize for Tagged<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            return self.0.serialize(serializer);
+        }
+
+        match Tagged::<C>::tag() {
+            Some(tag) => {
+                let mut variant = serializer.serialize_tuple_variant(TAG_ENUM, 1, TAGGED, 2)?;
+                variant.serialize_field(&tag)?;
+                variant.serialize_field(&self.0)?;
+                variant.end()
+            }
+            None => serializer.serialize_newtype_variant(TAG_ENUM, 0, UNTAGGED, &self.0),
+        }
+    }
+}
+
+/// Variant of the tag enum, identified by name as with ciborium or by index otherwise
+enum TagVariant {
+    Untagged,
+    Tagged,
+}
+
+impl<'de> serde::Deserialize<'de> for TagVariant {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(TagVariantVisitor)
+    }
+}
+
+struct TagVariantVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TagVariantVisitor {
+    type Value = TagVariant;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CBOR tag variant")
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match value {
+            0 => Ok(TagVariant::Untagged),
+            1 => Ok(TagVariant::Tagged),
+            _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        match value {
+            UNTAGGED => Ok(TagVariant::Untagged),
+            TAGGED => Ok(TagVariant::Tagged),
+            _ => Err(E::unknown_variant(value, &[UNTAGGED, TAGGED])),
+        }
+    }
+}
+
+struct TaggedVisitor<C: Config>(PhantomData<fn() -> C>);
+
+impl<'de, C: Config> serde::de::Visitor<'de> for TaggedVisitor<C> {
+    type Value = Tagged<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("byte string with an optional CBOR tag 21, 22 or 23")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'de>,
+    {
+        use serde_crate::de::VariantAccess;
+
+        match data.variant()? {
+            (TagVariant::Untagged, variant) => variant.newtype_variant().map(Tagged),
+            (TagVariant::Tagged, variant) => variant.tuple_variant(2, self),
+        }
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        let tag: u64 = visitor
+            .next_element()?
+            .ok_or_else(|| V::Error::invalid_length(0, &self))?;
+
+        if !(TAG_BASE64URL..=TAG_BASE16).contains(&tag) {
+            return Err(V::Error::invalid_value(
+                Unexpected::Unsigned(tag),
+                &"CBOR tag 21, 22 or 23",
+            ));
+        }
+
+        visitor
+            .next_element()?
+            .map(Tagged)
+            .ok_or_else(|| V::Error::invalid_length(1, &self))
+    }
+}
+
+impl<'de, C: Config> serde::Deserialize<'de> for Tagged<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return Blob::deserialize(deserializer).map(Tagged);
+        }
+
+        let visitor = TaggedVisitor(PhantomData);
+
+        deserializer.deserialize_enum(TAG_ENUM, &[UNTAGGED, TAGGED], visitor)
+    }
+}
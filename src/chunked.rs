@@ -0,0 +1,483 @@
+//! Blobs stored in fixed-size segments, for very large data
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::any;
+use core::convert::Infallible;
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+use base64::DecodeError;
+
+#[cfg(feature = "serde")]
+use super::{check_raw_len, BlobVisitor, SeqByte};
+use super::{whitespace, Blob, Config, Error, Standard};
+
+/// Default length of each segment of a `ChunkedBlob`, 1 MiB
+pub const DEFAULT_SEGMENT_LEN: usize = 1 << 20;
+
+/// Blob structure storing its binary data in a list of fixed-size segments
+///
+/// Appending never moves existing bytes, which avoids the reallocation spikes and
+/// fragmentation of one huge contiguous buffer. Configs with a
+/// [`BLOCK_LEN`](trait.Config.html#associatedconstant.BLOCK_LEN) encode segment by segment,
+/// while others are first copied into a contiguous buffer. It encodes, displays and
+/// serializes exactly as a `Blob<C>`, though binary formats also need a contiguous copy.
+pub struct ChunkedBlob<C: Config = Standard> {
+    segments: Vec<Vec<u8>>,
+    segment_len: usize,
+    len: usize,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> ChunkedBlob<C> {
+    /// Create a new empty `ChunkedBlob`, with segments of `DEFAULT_SEGMENT_LEN` bytes
+    #[inline]
+    pub fn new() -> ChunkedBlob<C> {
+        ChunkedBlob::with_segment_len(DEFAULT_SEGMENT_LEN)
+    }
+
+    /// Create a new empty `ChunkedBlob`, with segments of `segment_len` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_len` is zero.
+    pub fn with_segment_len(segment_len: usize) -> ChunkedBlob<C> {
+        assert!(
+            segment_len > 0,
+            "ChunkedBlob segment length must not be zero"
+        );
+
+        ChunkedBlob {
+            segments: Vec::new(),
+            segment_len,
+            len: 0,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode a `ChunkedBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// Configs with an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN) decode
+    /// block by block straight into the segments, while others, and configs requiring the
+    /// canonical form, first decode into a contiguous buffer.
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<ChunkedBlob<C>, Error> {
+        let encoded = encoded.as_ref();
+
+        match C::ENCODED_BLOCK_LEN {
+            Some(quantum) if !C::REQUIRE_CANONICAL => {
+                ChunkedBlob::decode_blocks(encoded, quantum, DEFAULT_SEGMENT_LEN)
+            }
+            _ => Blob::decode_base64(encoded).map(ChunkedBlob::from),
+        }
+    }
+
+    /// Decodes `encoded` into segments of `segment_len` bytes, a few blocks of `quantum`
+    /// symbols at a time
+    fn decode_blocks(
+        encoded: &[u8],
+        quantum: usize,
+        segment_len: usize,
+    ) -> Result<ChunkedBlob<C>, Error> {
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        let config = any::type_name::<C>();
+        let block_len = C::decoded_len_bounds(quantum).1;
+        let mut blob = ChunkedBlob::with_segment_len(segment_len);
+        let mut offset = 0;
+
+        while offset < encoded.len() {
+            let room = match blob.segments.last() {
+                Some(segment) if segment.len() < segment_len => segment_len - segment.len(),
+                _ => {
+                    blob.segments.push(Vec::with_capacity(segment_len));
+                    segment_len
+                }
+            };
+
+            // as many blocks as fill the segment, or one block split across two of them
+            let blocks = room / block_len;
+            let rest = &encoded[offset..];
+            let mut end = offset + symbols_prefix(rest, blocks.max(1) * quantum);
+
+            // only the final chunk, up to any trailing whitespace, may be padded
+            let last = encoded[end..].iter().all(u8::is_ascii_whitespace);
+
+            if last {
+                end = encoded.len();
+            } else if let Some(j) = encoded[offset..end].iter().position(|&c| c == b'=') {
+                let err = DecodeError::InvalidByte(offset + j, b'=');
+
+                return Err(Error::new(err, config, encoded.len()));
+            }
+
+            let piece = &encoded[offset..end];
+            let decode = |output: &mut Vec<u8>| {
+                C::decode(piece, output).map_err(|err| {
+                    Error::new(
+                        whitespace::shift_offsets(err, offset),
+                        config,
+                        encoded.len(),
+                    )
+                })
+            };
+
+            if blocks > 0 {
+                let segment = blob.segments.last_mut().unwrap();
+                let start = segment.len();
+
+                decode(segment)?;
+                blob.len += segment.len() - start;
+            } else {
+                let mut block = Vec::with_capacity(block_len);
+
+                decode(&mut block)?;
+                blob.extend_from_slice(&block);
+            }
+
+            offset = end;
+        }
+
+        Ok(blob)
+    }
+
+    /// Returns the total number of bytes
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bytes
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the length of each segment
+    #[inline]
+    pub fn segment_len(&self) -> usize {
+        self.segment_len
+    }
+
+    /// Appends a byte
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends the given bytes, filling the last segment before allocating new ones
+    pub fn extend_from_slice(&mut self, mut bytes: &[u8]) {
+        self.len += bytes.len();
+
+        while !bytes.is_empty() {
+            let segment_len = self.segment_len;
+
+            let segment = match self.segments.last_mut() {
+                Some(segment) if segment.len() < segment_len => segment,
+                _ => {
+                    self.segments.push(Vec::with_capacity(segment_len));
+                    self.segments.last_mut().unwrap()
+                }
+            };
+
+            let n = bytes.len().min(segment_len - segment.len());
+
+            segment.extend_from_slice(&bytes[..n]);
+            bytes = &bytes[n..];
+        }
+    }
+
+    /// Iterates over the segments, all of which are full except possibly the last
+    #[inline]
+    pub fn segments(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        self.segments.iter().map(|segment| &segment[..])
+    }
+
+    /// Iterates over the bytes
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.segments().flat_map(|segment| segment.iter().cloned())
+    }
+
+    /// Copy the bytes into a contiguous `Blob`
+    pub fn to_blob(&self) -> Blob<C> {
+        let mut data = Vec::with_capacity(self.len);
+
+        for segment in self.segments() {
+            data.extend_from_slice(segment);
+        }
+
+        Blob::from_vec(data)
+    }
+
+    /// Encode the `ChunkedBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        let _: Result<(), Infallible> = self.for_each_block(|bytes| {
+            C::encode(bytes, &mut encoded);
+            Ok(())
+        });
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, segment by segment where the config allows it
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        self.for_each_block(|bytes| C::encode_to(bytes, &mut writer))
+    }
+
+    /// Calls `f` with consecutive pieces of the bytes that can be encoded independently
+    fn for_each_block<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&[u8]) -> Result<(), E>,
+    {
+        let block_len = match C::BLOCK_LEN {
+            Some(block_len) if self.segments.len() > 1 => block_len,
+            _ if self.segments.len() == 1 => return f(&self.segments[0]),
+            _ => return f(&self.to_blob()),
+        };
+
+        // bytes of a block split across segments
+        let mut carry = Vec::with_capacity(block_len);
+
+        for mut segment in self.segments() {
+            if !carry.is_empty() {
+                let n = segment.len().min(block_len - carry.len());
+
+                carry.extend_from_slice(&segment[..n]);
+                segment = &segment[n..];
+
+                if carry.len() < block_len {
+                    continue;
+                }
+
+                f(&carry)?;
+                carry.clear();
+            }
+
+            let whole = segment.len() - segment.len() % block_len;
+
+            f(&segment[..whole])?;
+            carry.extend_from_slice(&segment[whole..]);
+        }
+
+        f(&carry)
+    }
+}
+
+/// Returns the length of the shortest prefix of `input` holding `n` symbols other than ASCII
+/// whitespace, or of all of `input` if it holds fewer
+fn symbols_prefix(input: &[u8], n: usize) -> usize {
+    let mut symbols = 0;
+
+    for (i, c) in input.iter().enumerate() {
+        if !c.is_ascii_whitespace() {
+            symbols += 1;
+
+            if symbols == n {
+                return i + 1;
+            }
+        }
+    }
+
+    input.len()
+}
+
+impl<C: Config> Default for ChunkedBlob<C> {
+    #[inline]
+    fn default() -> ChunkedBlob<C> {
+        ChunkedBlob::new()
+    }
+}
+
+impl<C: Config> Clone for ChunkedBlob<C> {
+    #[inline]
+    fn clone(&self) -> ChunkedBlob<C> {
+        ChunkedBlob {
+            segments: self.segments.clone(),
+            segment_len: self.segment_len,
+            len: self.len,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> fmt::Debug for ChunkedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ChunkedBlob")
+            .field("len", &self.len)
+            .field("segment_len", &self.segment_len)
+            .finish()
+    }
+}
+
+impl<C: Config> Display for ChunkedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.for_each_block(|bytes| C::fmt(bytes, f))
+    }
+}
+
+/// Decodes a `ChunkedBlob` from a string, as `Blob` would
+impl<C: Config> FromStr for ChunkedBlob<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, trimmed) = whitespace::trim(s);
+
+        // offsets are reported relative to the untrimmed string
+        ChunkedBlob::decode_base64(trimmed).map_err(|err| err.within(offset, s.len()))
+    }
+}
+
+/// Compares the bytes, regardless of segment length
+impl<C: Config> PartialEq for ChunkedBlob<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<C: Config> Eq for ChunkedBlob<C> {}
+
+impl<C: Config> PartialEq<Blob<C>> for ChunkedBlob<C> {
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter().cloned())
+    }
+}
+
+impl<C: Config> Extend<u8> for ChunkedBlob<C> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl<'a, C: Config> From<&'a [u8]> for ChunkedBlob<C> {
+    #[inline]
+    fn from(bytes: &'a [u8]) -> ChunkedBlob<C> {
+        let mut blob = ChunkedBlob::new();
+
+        blob.extend_from_slice(bytes);
+
+        blob
+    }
+}
+
+impl<C: Config> From<Blob<C>> for ChunkedBlob<C> {
+    #[inline]
+    fn from(blob: Blob<C>) -> ChunkedBlob<C> {
+        ChunkedBlob::from(&blob[..])
+    }
+}
+
+impl<C: Config> From<ChunkedBlob<C>> for Blob<C> {
+    #[inline]
+    fn from(blob: ChunkedBlob<C>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+/// Writes the encoded form straight to the serializer in human-readable formats
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for ChunkedBlob<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // binary formats need the bytes in one piece
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.to_blob());
+        }
+
+        if C::SERIALIZE_AS_SEQ {
+            return serializer.collect_seq(self.iter());
+        }
+
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for ChunkedBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = ChunkedVisitor {
+            _config: PhantomData,
+        };
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+/// Deserializes a `ChunkedBlob` as a `Blob` would be, without a contiguous copy of the bytes
+#[cfg(feature = "serde")]
+struct ChunkedVisitor<C: Config> {
+    _config: PhantomData<fn() -> C>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::de::Visitor<'de> for ChunkedVisitor<C> {
+    type Value = ChunkedBlob<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        ChunkedBlob::from_str(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(ChunkedBlob::from(value))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        let mut blob = ChunkedBlob::new();
+
+        while let Some(SeqByte(byte)) = visitor.next_element()? {
+            check_raw_len::<C, V::Error>(blob.len() + 1)?;
+
+            blob.push(byte);
+        }
+
+        Ok(blob)
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        BlobVisitor::<C>::new(false)
+            .visit_map(visitor)
+            .map(ChunkedBlob::from)
+    }
+}
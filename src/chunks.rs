@@ -0,0 +1,82 @@
+//! Encoding blobs in independently decodable pieces
+
+use alloc_crate::string::String;
+use core::fmt;
+use core::marker::PhantomData;
+use core::slice::Chunks;
+
+use super::{Blob, Config, Standard};
+
+/// Iterator over independently decodable pieces of the encoded form of a `Blob`, as returned
+/// by [`Blob::encode_chunks`]
+///
+/// [`Blob::encode_chunks`]: struct.Blob.html#method.encode_chunks
+pub struct EncodeChunks<'a, C: Config = Standard> {
+    chunks: Chunks<'a, u8>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'a, C: Config> EncodeChunks<'a, C> {
+    /// Create an iterator encoding at most `len` bytes of the given `Blob` per chunk
+    ///
+    /// The length is rounded down to a whole number of blocks of `C`, but is at least one
+    /// block. Configs without a
+    /// [`BLOCK_LEN`](trait.Config.html#associatedconstant.BLOCK_LEN) encode as a single chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    pub fn new(blob: &'a Blob<C>, len: usize) -> EncodeChunks<'a, C> {
+        assert!(len > 0, "EncodeChunks chunk length must not be zero");
+
+        let len = match C::BLOCK_LEN {
+            Some(block_len) => (len / block_len).max(1) * block_len,
+            None => blob.len().max(1),
+        };
+
+        EncodeChunks {
+            chunks: blob.chunks(len),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> Clone for EncodeChunks<'a, C> {
+    #[inline]
+    fn clone(&self) -> EncodeChunks<'a, C> {
+        EncodeChunks {
+            chunks: self.chunks.clone(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> fmt::Debug for EncodeChunks<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EncodeChunks")
+            .field("remaining", &self.chunks.len())
+            .finish()
+    }
+}
+
+impl<'a, C: Config> Iterator for EncodeChunks<'a, C> {
+    type Item = String;
+
+    #[inline]
+    fn next(&mut self) -> Option<String> {
+        self.chunks.next().map(|chunk| {
+            let mut encoded = String::new();
+
+            C::encode(chunk, &mut encoded);
+
+            encoded
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, C: Config> ExactSizeIterator for EncodeChunks<'a, C> {}
@@ -0,0 +1,192 @@
+//! Content addressing with multihashes and CIDs
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use sha2::{Digest, Sha256, Sha512};
+
+use super::constant_time_eq;
+use super::{Base58, Config, Multibase};
+
+/// Hash functions for multihashes, identified by their multicodec code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multihash {
+    /// The bytes themselves, code `0x00`, for content too short to be worth hashing
+    Identity,
+    /// SHA-256, code `0x12`, the default for IPFS
+    Sha2_256,
+    /// SHA-512, code `0x13`
+    Sha2_512,
+    /// BLAKE3 with a 32-byte output, code `0x1e`
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl Multihash {
+    /// Returns the multicodec code for this hash function
+    pub fn code(self) -> u64 {
+        match self {
+            Multihash::Identity => 0x00,
+            Multihash::Sha2_256 => 0x12,
+            Multihash::Sha2_512 => 0x13,
+            #[cfg(feature = "blake3")]
+            Multihash::Blake3 => 0x1e,
+        }
+    }
+
+    /// Returns the hash function identified by the given multicodec code, if supported
+    pub fn from_code(code: u64) -> Option<Multihash> {
+        Some(match code {
+            0x00 => Multihash::Identity,
+            0x12 => Multihash::Sha2_256,
+            0x13 => Multihash::Sha2_512,
+            #[cfg(feature = "blake3")]
+            0x1e => Multihash::Blake3,
+            _ => return None,
+        })
+    }
+
+    /// Returns the full digest of `data`
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Multihash::Identity => data.to_vec(),
+            Multihash::Sha2_256 => Sha256::digest(data).to_vec(),
+            Multihash::Sha2_512 => Sha512::digest(data).to_vec(),
+            #[cfg(feature = "blake3")]
+            Multihash::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Content types of CIDs, identified by their multicodec code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multicodec {
+    /// Opaque bytes, code `0x55`
+    Raw,
+    /// JSON, code `0x0200`
+    Json,
+    /// MerkleDAG protobuf, code `0x70`, the content type of every CIDv0
+    DagPb,
+    /// MerkleDAG CBOR, code `0x71`
+    DagCbor,
+    /// MerkleDAG JSON, code `0x0129`
+    DagJson,
+}
+
+impl Multicodec {
+    /// Returns the multicodec code for this content type
+    pub fn code(self) -> u64 {
+        match self {
+            Multicodec::Raw => 0x55,
+            Multicodec::Json => 0x0200,
+            Multicodec::DagPb => 0x70,
+            Multicodec::DagCbor => 0x71,
+            Multicodec::DagJson => 0x0129,
+        }
+    }
+
+    /// Returns the content type identified by the given multicodec code, if supported
+    pub fn from_code(code: u64) -> Option<Multicodec> {
+        Some(match code {
+            0x55 => Multicodec::Raw,
+            0x0200 => Multicodec::Json,
+            0x70 => Multicodec::DagPb,
+            0x71 => Multicodec::DagCbor,
+            0x0129 => Multicodec::DagJson,
+            _ => return None,
+        })
+    }
+}
+
+/// Appends `n` as an unsigned LEB128 varint
+fn write_varint(mut n: u64, output: &mut Vec<u8>) {
+    while n >= 0x80 {
+        output.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+
+    output.push(n as u8);
+}
+
+/// Reads an unsigned LEB128 varint from the start of `input`, in its minimal form of at most
+/// 9 bytes as multiformats requires
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut n = 0;
+
+    for (i, &b) in input.iter().enumerate().take(9) {
+        n |= u64::from(b & 0x7f) << (7 * i);
+
+        if b & 0x80 == 0 {
+            if b == 0 && i > 0 {
+                return None;
+            }
+
+            *input = &input[i + 1..];
+
+            return Some(n);
+        }
+    }
+
+    None
+}
+
+/// Appends the multihash of `data` to `output`
+pub(crate) fn multihash(hash: Multihash, data: &[u8], output: &mut Vec<u8>) {
+    let digest = hash.digest(data);
+
+    write_varint(hash.code(), output);
+    write_varint(digest.len() as u64, output);
+    output.extend_from_slice(&digest);
+}
+
+/// Returns `true` if `expected` is a multihash of `data` with a supported hash function
+///
+/// Truncated digests are rejected, since a short enough one would match any data.
+pub(crate) fn verify_multihash(mut expected: &[u8], data: &[u8]) -> bool {
+    let hash = match read_varint(&mut expected).and_then(Multihash::from_code) {
+        Some(hash) => hash,
+        None => return false,
+    };
+
+    match read_varint(&mut expected) {
+        Some(len) if len == expected.len() as u64 => constant_time_eq(&hash.digest(data), expected),
+        _ => false,
+    }
+}
+
+/// Returns the CIDv1 of `data` as a lowercase base-32 multibase string
+pub(crate) fn cid(codec: Multicodec, hash: Multihash, data: &[u8]) -> String {
+    let mut cid = Vec::new();
+
+    write_varint(1, &mut cid);
+    write_varint(codec.code(), &mut cid);
+    multihash(hash, data, &mut cid);
+
+    let mut encoded = String::new();
+
+    Multibase::Base32.encode(&cid, &mut encoded);
+
+    encoded
+}
+
+/// Returns `true` if `expected` is a CIDv0 or CIDv1 string addressing `data`
+pub(crate) fn verify_cid(expected: &str, data: &[u8]) -> bool {
+    let mut cid = Vec::new();
+
+    // a CIDv0 is a bare base-58 SHA-256 multihash
+    if expected.len() == 46 && expected.starts_with("Qm") {
+        return Base58::decode(expected.as_bytes(), &mut cid).is_ok()
+            && verify_multihash(&cid, data);
+    }
+
+    if Multibase::decode(expected.as_bytes(), &mut cid).is_err() {
+        return false;
+    }
+
+    let mut cid = &cid[..];
+
+    // the content type does not affect the digest
+    read_varint(&mut cid) == Some(1)
+        && read_varint(&mut cid).is_some()
+        && verify_multihash(cid, data)
+}
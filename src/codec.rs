@@ -0,0 +1,148 @@
+//! Native, non-serde binary encodings of blobs
+
+use core::any;
+
+use super::{Config, Error};
+
+/// Returns the error for a blob of `len` bytes, if it exceeds `C::MAX_DECODED_LEN`
+fn check_len<C: Config>(len: usize) -> Option<Error> {
+    match C::MAX_DECODED_LEN {
+        Some(max) if len > max => Some(Error::too_long(max, any::type_name::<C>(), len)),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "bincode")]
+mod bincode_impls {
+    use alloc_crate::string::ToString;
+    use core::convert::TryFrom;
+
+    use bincode::de::read::Reader;
+    use bincode::de::{BorrowDecoder, Decoder};
+    use bincode::enc::Encoder;
+    use bincode::error::{DecodeError, EncodeError};
+    use bincode::{BorrowDecode, Decode, Encode};
+
+    use super::super::{Blob, Config};
+    use super::check_len;
+
+    /// Encoded as a length-prefixed byte slice, exactly as `Vec<u8>` would be
+    impl<C: Config> Encode for Blob<C> {
+        #[inline]
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.data.encode(encoder)
+        }
+    }
+
+    impl<Context, C: Config> Decode<Context> for Blob<C> {
+        fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            let len = u64::decode(decoder)?;
+            let len = usize::try_from(len).map_err(|_| DecodeError::OutsideUsizeRange(len))?;
+
+            // reject oversized blobs before allocating for them
+            if let Some(err) = check_len::<C>(len) {
+                return Err(DecodeError::OtherString(err.to_string()));
+            }
+
+            decoder.claim_container_read::<u8>(len)?;
+
+            let mut data = vec![0; len];
+            decoder.reader().read(&mut data)?;
+
+            Ok(Blob::from_vec(data))
+        }
+    }
+
+    impl<'de, Context, C: Config> BorrowDecode<'de, Context> for Blob<C> {
+        #[inline]
+        fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+            decoder: &mut D,
+        ) -> Result<Self, DecodeError> {
+            Blob::decode(decoder)
+        }
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod borsh_impls {
+    use alloc_crate::vec::Vec;
+
+    use borsh::io::{self, Read, Write};
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    use super::super::{Blob, Config};
+    use super::check_len;
+
+    /// Serialized as a `u32` length followed by the raw bytes, exactly as `Vec<u8>` would be
+    impl<C: Config> BorshSerialize for Blob<C> {
+        #[inline]
+        fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+            self.data.serialize(writer)
+        }
+    }
+
+    impl<C: Config> BorshDeserialize for Blob<C> {
+        fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+            let len = u32::deserialize_reader(reader)? as usize;
+
+            if let Some(err) = check_len::<C>(len) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+            }
+
+            // grow the buffer as bytes arrive, rather than trusting the length up front
+            let mut data = Vec::new();
+            reader.take(len as u64).read_to_end(&mut data)?;
+
+            if data.len() != len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+
+            Ok(Blob::from_vec(data))
+        }
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impls {
+    use rkyv::rancor::{Fallible, Source};
+    use rkyv::ser::{Allocator, Writer};
+    use rkyv::vec::{ArchivedVec, VecResolver};
+    use rkyv::{Archive, Deserialize, Place, Serialize};
+
+    use super::super::{Blob, Config};
+    use super::check_len;
+
+    /// Archived as the raw bytes, exactly as `Vec<u8>` would be
+    impl<C: Config> Archive for Blob<C> {
+        type Archived = ArchivedVec<u8>;
+        type Resolver = VecResolver;
+
+        #[inline]
+        fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            ArchivedVec::resolve_from_slice(&self.data, resolver, out);
+        }
+    }
+
+    impl<S, C: Config> Serialize<S> for Blob<C>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        #[inline]
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::<u8>::serialize_from_slice(&self.data, serializer)
+        }
+    }
+
+    impl<D, C: Config> Deserialize<Blob<C>, D> for ArchivedVec<u8>
+    where
+        D: Fallible + ?Sized,
+        D::Error: Source,
+    {
+        fn deserialize(&self, _deserializer: &mut D) -> Result<Blob<C>, D::Error> {
+            match check_len::<C>(self.len()) {
+                Some(err) => Err(D::Error::new(err)),
+                None => Ok(Blob::from(self.as_slice())),
+            }
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Constant-time comparison of blobs through `subtle`
+
+use subtle::{Choice, ConstantTimeEq};
+
+#[cfg(feature = "zeroize")]
+use super::SecretBlob;
+use super::{Blob, Config, FixedBlob};
+
+/// Compares all bytes of blobs of the same length, as `Blob::ct_eq` does
+impl<C: Config> ConstantTimeEq for Blob<C> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+impl<C: Config, const N: usize> ConstantTimeEq for FixedBlob<C, N> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<C: Config> ConstantTimeEq for SecretBlob<C> {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.expose_secret()[..].ct_eq(&other.expose_secret()[..])
+    }
+}
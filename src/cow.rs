@@ -0,0 +1,267 @@
+//! Blobs that may borrow their bytes, for zero-copy deserialization
+
+use alloc_crate::borrow::Cow;
+#[cfg(feature = "serde")]
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "serde")]
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::{check_raw_len, serialize_bytes};
+use super::{Blob, Config, Standard};
+
+/// Blob structure that either borrows or owns its binary data
+///
+/// When deserializing, bytes lent by the deserializer, such as from bincode or postcard
+/// input held in memory, are borrowed instead of copied. Encoded strings must still be
+/// decoded, so their bytes are always owned.
+pub struct CowBlob<'a, C: Config = Standard> {
+    data: Cow<'a, [u8]>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'a, C: Config> CowBlob<'a, C> {
+    /// Create a `CowBlob` borrowing the given bytes
+    #[inline]
+    pub fn borrowed(data: &'a [u8]) -> CowBlob<'a, C> {
+        CowBlob {
+            data: Cow::Borrowed(data),
+            _config: PhantomData,
+        }
+    }
+
+    /// Create a `CowBlob` owning the given bytes
+    #[inline]
+    pub fn owned(data: Vec<u8>) -> CowBlob<'a, C> {
+        CowBlob {
+            data: Cow::Owned(data),
+            _config: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the bytes are borrowed rather than owned
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        match self.data {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        }
+    }
+
+    /// Encode the `CowBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Convert into an owned `Blob`, copying the bytes if they are borrowed
+    #[inline]
+    pub fn into_owned(self) -> Blob<C> {
+        Blob::from_vec(self.data.into_owned())
+    }
+
+    /// Consume self and return the inner `Cow<[u8]>`
+    #[inline]
+    pub fn into_cow(self) -> Cow<'a, [u8]> {
+        self.data
+    }
+}
+
+impl<'a, C: Config> Clone for CowBlob<'a, C> {
+    #[inline]
+    fn clone(&self) -> CowBlob<'a, C> {
+        CowBlob {
+            data: self.data.clone(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> fmt::Debug for CowBlob<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CowBlob").field(&self.data).finish()
+    }
+}
+
+impl<'a, C: Config> Display for CowBlob<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+impl<'a, C: Config> PartialEq for CowBlob<'a, C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<'a, C: Config> Eq for CowBlob<'a, C> {}
+
+impl<'a, C: Config> Hash for CowBlob<'a, C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl<'a, C: Config> Deref for CowBlob<'a, C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a, C: Config> AsRef<[u8]> for CowBlob<'a, C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<'a, C: Config> From<&'a [u8]> for CowBlob<'a, C> {
+    #[inline(always)]
+    fn from(data: &'a [u8]) -> CowBlob<'a, C> {
+        CowBlob::borrowed(data)
+    }
+}
+
+impl<'a, C: Config> From<Cow<'a, [u8]>> for CowBlob<'a, C> {
+    #[inline(always)]
+    fn from(data: Cow<'a, [u8]>) -> CowBlob<'a, C> {
+        CowBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> From<CowBlob<'a, C>> for Blob<C> {
+    #[inline(always)]
+    fn from(blob: CowBlob<'a, C>) -> Blob<C> {
+        blob.into_owned()
+    }
+}
+
+impl<'a, C: Config> From<Blob<C>> for CowBlob<'a, C> {
+    #[inline(always)]
+    fn from(blob: Blob<C>) -> CowBlob<'a, C> {
+        CowBlob::owned(blob.into_vec())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, C: Config> serde::Serialize for CowBlob<'a, C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CowBlobVisitor<C: Config> {
+    _config: PhantomData<fn() -> C>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::de::Visitor<'de> for CowBlobVisitor<C> {
+    type Value = CowBlob<'de, C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Blob::from_str(value).map(CowBlob::from).map_err(E::custom)
+    }
+
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(CowBlob::borrowed(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(CowBlob::owned(value.to_owned()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(CowBlob::owned(value))
+    }
+
+    fn visit_seq<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        serde::de::Visitor::visit_seq(super::BlobVisitor::<C>::new(false), visitor)
+            .map(CowBlob::from)
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        serde::de::Visitor::visit_map(super::BlobVisitor::<C>::new(false), visitor)
+            .map(CowBlob::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for CowBlob<'de, C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = CowBlobVisitor {
+            _config: PhantomData,
+        };
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Base-64 with custom alphabets
+
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::error::Error;
+use core::fmt::{self, Display};
+use core::str;
+
+use base64::alphabet::Alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::{DecodeError, Engine};
+
+/// Error returned when a custom base-64 alphabet is invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// The alphabet did not contain exactly 64 bytes
+    InvalidLength(usize),
+    /// The alphabet contained a byte that is not printable ASCII, or the `=` padding byte
+    InvalidByte(u8),
+    /// The alphabet contained the same byte more than once
+    DuplicateByte(u8),
+}
+
+impl Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AlphabetError::InvalidLength(len) => {
+                write!(f, "Alphabet must contain 64 bytes, got {}", len)
+            }
+            AlphabetError::InvalidByte(byte) => write!(f, "Invalid alphabet byte {:#x}", byte),
+            AlphabetError::DuplicateByte(byte) => write!(f, "Duplicate alphabet byte {:#x}", byte),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+/// Validates a base-64 alphabet
+pub const fn alphabet(alphabet: &[u8]) -> Result<Alphabet, AlphabetError> {
+    if alphabet.len() != 64 {
+        return Err(AlphabetError::InvalidLength(alphabet.len()));
+    }
+
+    let mut seen = [false; 256];
+    let mut i = 0;
+
+    while i < 64 {
+        let c = alphabet[i];
+
+        if c < b'!' || c > b'~' || c == b'=' {
+            return Err(AlphabetError::InvalidByte(c));
+        }
+
+        if seen[c as usize] {
+            return Err(AlphabetError::DuplicateByte(c));
+        }
+
+        seen[c as usize] = true;
+
+        i += 1;
+    }
+
+    // the alphabet is now known to be printable ASCII, which `Alphabet` also accepts
+    match str::from_utf8(alphabet) {
+        Ok(alphabet) => match Alphabet::new(alphabet) {
+            Ok(alphabet) => Ok(alphabet),
+            Err(_) => Err(AlphabetError::InvalidLength(64)),
+        },
+        Err(_) => Err(AlphabetError::InvalidLength(64)),
+    }
+}
+
+/// Validates a base-64 alphabet at compile time, for use by `define_config!`
+pub const fn alphabet_or_panic(alphabet: &[u8]) -> Alphabet {
+    match self::alphabet(alphabet) {
+        Ok(alphabet) => alphabet,
+        Err(AlphabetError::InvalidLength(_)) => panic!("alphabet must contain exactly 64 bytes"),
+        Err(AlphabetError::InvalidByte(_)) => {
+            panic!("alphabet must only contain printable ASCII, excluding `=`")
+        }
+        Err(AlphabetError::DuplicateByte(_)) => panic!("alphabet must not contain duplicate bytes"),
+    }
+}
+
+/// Builds an engine for the given alphabet, as used by all base-64 configs
+///
+/// Decoding accepts input both with and without padding, but rejects non-zero trailing bits.
+pub const fn engine(alphabet: &Alphabet, pad: bool) -> GeneralPurpose {
+    let config = GeneralPurposeConfig::new()
+        .with_encode_padding(pad)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent);
+
+    GeneralPurpose::new(alphabet, config)
+}
+
+/// Returns a copy of `input` with the trailing bits of its last symbol, at `last`, cleared
+pub fn clear_trailing_bits<E: Engine>(engine: &E, input: &[u8], last: usize) -> Vec<u8> {
+    let mut cleared = input.to_owned();
+
+    clear_trailing_bits_in_place(engine, &mut cleared, last);
+
+    cleared
+}
+
+/// Clears the trailing bits of the last symbol of `input`, at `last`, without allocating
+///
+/// The alphabet is recovered from `engine` by encoding every symbol value in order.
+pub fn clear_trailing_bits_in_place<E: Engine>(engine: &E, input: &mut [u8], last: usize) {
+    let mut values = [0u8; 48];
+
+    for (i, chunk) in values.chunks_mut(3).enumerate() {
+        let v = i as u32 * 4;
+        let n = v << 18 | (v + 1) << 12 | (v + 2) << 6 | (v + 3);
+
+        chunk.copy_from_slice(&n.to_be_bytes()[1..]);
+    }
+
+    // 48 bytes are exactly 64 symbols, without padding
+    let mut alphabet = [0u8; 64];
+
+    if engine.encode_slice(values, &mut alphabet).is_err() {
+        return;
+    }
+
+    // the final group of 2 or 3 symbols carries 4 or 2 trailing bits
+    let mask = if last % 4 == 1 { 0x30 } else { 0x3c };
+
+    if let Some(value) = alphabet.iter().position(|&c| c == input[last]) {
+        input[last] = alphabet[value & mask];
+    }
+}
+
+/// Base-64 encoding configuration with a custom alphabet, chosen at runtime
+///
+/// See [`Blob::encode_with`](struct.Blob.html#method.encode_with) and
+/// [`Blob::decode_with`](struct.Blob.html#method.decode_with).
+#[derive(Clone)]
+pub struct DynConfig {
+    alphabet: Alphabet,
+    engine: GeneralPurpose,
+    pad: bool,
+}
+
+impl DynConfig {
+    /// Create a new config from a 64-byte alphabet, and whether to pad when encoding
+    ///
+    /// The alphabet must consist of unique printable ASCII characters, excluding `=`.
+    pub fn new<A>(alphabet: A, pad: bool) -> Result<DynConfig, AlphabetError>
+    where
+        A: AsRef<[u8]>,
+    {
+        self::alphabet(alphabet.as_ref()).map(|alphabet| DynConfig {
+            engine: engine(&alphabet, pad),
+            alphabet,
+            pad,
+        })
+    }
+
+    /// Returns the alphabet of this config
+    pub fn alphabet(&self) -> &str {
+        self.alphabet.as_str()
+    }
+
+    /// Returns `true` if encoding with this config adds padding
+    #[inline]
+    pub fn is_padded(&self) -> bool {
+        self.pad
+    }
+
+    #[inline]
+    pub(crate) fn encode(&self, input: &[u8], output: &mut String) {
+        self.engine.encode_string(input, output)
+    }
+
+    #[inline]
+    pub(crate) fn decode(&self, input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+        self.engine.decode_vec(input, output)
+    }
+}
+
+impl fmt::Debug for DynConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DynConfig")
+            .field("alphabet", &self.alphabet())
+            .field("pad", &self.pad)
+            .finish()
+    }
+}
+
+impl PartialEq for DynConfig {
+    fn eq(&self, other: &DynConfig) -> bool {
+        self.alphabet == other.alphabet && self.pad == other.pad
+    }
+}
+
+impl Eq for DynConfig {}
@@ -0,0 +1,129 @@
+//! `data:` URLs, as described in RFC 2397
+
+use alloc_crate::borrow::Cow;
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+
+use super::{Blob, Config, Error, Standard};
+
+/// Media type of a data URL which does not specify one
+const DEFAULT_MEDIA_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// Error returned when decoding a data URL into a `Blob`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataUrlError {
+    /// The base-64 payload was invalid
+    Decode(Error),
+    /// The `data:` scheme or the comma before the payload was missing, or a `%` was not
+    /// followed by two hexadecimal digits
+    Malformed,
+}
+
+impl Display for DataUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DataUrlError::Decode(ref err) => err.fmt(f),
+            DataUrlError::Malformed => f.write_str("Malformed data URL"),
+        }
+    }
+}
+
+impl StdError for DataUrlError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            DataUrlError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for DataUrlError {
+    #[inline]
+    fn from(err: Error) -> DataUrlError {
+        DataUrlError::Decode(err)
+    }
+}
+
+/// Appends the base-64 data URL of `data` to `output`
+pub(crate) fn encode(media_type: &str, data: &[u8], output: &mut String) {
+    output.reserve(media_type.len() + 13 + data.len().div_ceil(3) * 4);
+
+    output.push_str("data:");
+    output.push_str(media_type);
+    output.push_str(";base64,");
+
+    Standard::encode(data, output);
+}
+
+/// Decodes `%XX` escapes in `input`, borrowing it if there are none
+fn percent_decode(input: &[u8]) -> Option<Cow<'_, [u8]>> {
+    if !input.contains(&b'%') {
+        return Some(Cow::Borrowed(input));
+    }
+
+    let mut output = Vec::with_capacity(input.len());
+    let mut bytes = input.iter();
+
+    while let Some(&c) = bytes.next() {
+        if c != b'%' {
+            output.push(c);
+            continue;
+        }
+
+        let hi = bytes.next().and_then(|&c| (c as char).to_digit(16))?;
+        let lo = bytes.next().and_then(|&c| (c as char).to_digit(16))?;
+
+        output.push((hi << 4 | lo) as u8);
+    }
+
+    Some(Cow::Owned(output))
+}
+
+/// Decodes a data URL, returning its media type and bytes
+pub(crate) fn decode(url: &[u8]) -> Result<(String, Blob<Standard>), DataUrlError> {
+    let input = url.trim_ascii();
+    let start = url.len() - url.trim_ascii_start().len();
+
+    if input.len() < 5 || !input[..5].eq_ignore_ascii_case(b"data:") {
+        return Err(DataUrlError::Malformed);
+    }
+
+    let comma = input
+        .iter()
+        .position(|&c| c == b',')
+        .ok_or(DataUrlError::Malformed)?;
+
+    let mut meta = input[5..comma].trim_ascii();
+    let payload = &input[comma + 1..];
+
+    let base64 = match meta.iter().rposition(|&c| c == b';') {
+        Some(i) if meta[i + 1..].trim_ascii().eq_ignore_ascii_case(b"base64") => {
+            meta = &meta[..i];
+            true
+        }
+        _ => false,
+    };
+
+    let meta = percent_decode(meta).ok_or(DataUrlError::Malformed)?;
+    let meta = String::from_utf8_lossy(&meta);
+
+    // RFC 2397 defaults the type, but keeps any parameters such as a charset
+    let media_type = if meta.is_empty() {
+        String::from(DEFAULT_MEDIA_TYPE)
+    } else if meta.starts_with(';') {
+        ["text/plain", &meta].concat()
+    } else {
+        meta.into_owned()
+    };
+
+    let blob = match percent_decode(payload).ok_or(DataUrlError::Malformed)? {
+        Cow::Borrowed(bytes) if base64 => Blob::<Standard>::decode_base64_lenient(bytes)
+            .map_err(|err| err.within(start + comma + 1, url.len()))?,
+        Cow::Owned(bytes) if base64 => Blob::<Standard>::decode_base64_lenient(bytes)?,
+        bytes => Blob::from(bytes.into_owned()),
+    };
+
+    Ok((media_type, blob))
+}
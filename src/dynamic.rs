@@ -0,0 +1,416 @@
+//! Blobs with an encoding chosen at runtime
+
+#[cfg(feature = "serde")]
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::any;
+use core::fmt::{self, Display};
+use core::ops::{Deref, DerefMut};
+
+use base64::DecodeError;
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+use super::Error;
+#[cfg(feature = "serde")]
+use super::{whitespace, BlobVisitor, SeqByte};
+use super::{
+    Ascii85, Base32, Base32Hex, Base32HexNoPad, Base32NoPad, Base58, Bcrypt, Blob, Config,
+    Crockford, CrockfordCheck, Crypt, Hex, HexUpper, ImapMutf7, Mime, Standard, StandardNoPad,
+    UrlSafe, UrlSafeNoPad,
+};
+
+macro_rules! encodings {
+    ($($name:ident,)*) => {
+        /// Encoding of one of the built-in configs, chosen at runtime
+        ///
+        /// Each variant is named after, and behaves exactly like, the corresponding config type.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Encoding {
+            $(
+                #[doc = concat!(
+                    "The [`", stringify!($name), "`](enum.", stringify!($name), ".html) config"
+                )]
+                $name,
+            )*
+        }
+
+        impl Encoding {
+            /// Every supported encoding
+            pub const ALL: &'static [Encoding] = &[$(Encoding::$name,)*];
+
+            /// Returns the name of this encoding, which is that of its config type
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Encoding::$name => stringify!($name),)*
+                }
+            }
+
+            /// Returns the encoding with the given name, ignoring ASCII case, if supported
+            pub fn from_name(name: &str) -> Option<Encoding> {
+                Encoding::ALL
+                    .iter()
+                    .find(|encoding| encoding.name().eq_ignore_ascii_case(name))
+                    .cloned()
+            }
+
+            /// Returns the type name of the config of this encoding
+            pub(crate) fn type_name(self) -> &'static str {
+                match self {
+                    $(Encoding::$name => any::type_name::<$name>(),)*
+                }
+            }
+
+            /// Appends the encoded form of `input` to `output`
+            pub(crate) fn encode(self, input: &[u8], output: &mut String) {
+                match self {
+                    $(Encoding::$name => $name::encode(input, output),)*
+                }
+            }
+
+            /// Decodes `input`, appending the decoded bytes to `output`
+            pub(crate) fn decode(
+                self,
+                input: &[u8],
+                output: &mut Vec<u8>,
+            ) -> Result<(), DecodeError> {
+                match self {
+                    $(Encoding::$name => $name::decode(input, output),)*
+                }
+            }
+
+            /// Decodes `input` like `decode`, but skipping any ASCII whitespace
+            pub(crate) fn decode_lenient(
+                self,
+                input: &[u8],
+                output: &mut Vec<u8>,
+            ) -> Result<(), DecodeError> {
+                match self {
+                    $(Encoding::$name => $name::decode_lenient(input, output),)*
+                }
+            }
+
+            /// Checks that `input` would decode, returning the decoded length
+            pub(crate) fn validate(self, input: &[u8]) -> Result<usize, DecodeError> {
+                match self {
+                    $(Encoding::$name => $name::validate(input),)*
+                }
+            }
+
+            /// Writes the encoded form of `input` to a formatter
+            fn fmt(self, input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(Encoding::$name => $name::fmt(input, f),)*
+                }
+            }
+        }
+
+        $(
+            impl From<Blob<$name>> for DynBlob {
+                #[inline]
+                fn from(blob: Blob<$name>) -> DynBlob {
+                    DynBlob::from_blob(blob, Encoding::$name)
+                }
+            }
+        )*
+    };
+}
+
+encodings! {
+    Standard,
+    StandardNoPad,
+    UrlSafe,
+    UrlSafeNoPad,
+    Crypt,
+    ImapMutf7,
+    Bcrypt,
+    Mime,
+    Hex,
+    HexUpper,
+    Base32,
+    Base32NoPad,
+    Base32Hex,
+    Base32HexNoPad,
+    Crockford,
+    CrockfordCheck,
+    Base58,
+    Ascii85,
+}
+
+impl Default for Encoding {
+    #[inline]
+    fn default() -> Encoding {
+        Encoding::Standard
+    }
+}
+
+impl Display for Encoding {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Binary data with an [`Encoding`](enum.Encoding.html) chosen at runtime
+///
+/// This mirrors `Blob`, but carries its encoding alongside the bytes instead of as a type
+/// parameter. Any `Blob` of a built-in config converts into a `DynBlob` of the matching
+/// encoding, and a `DynBlob` converts into a `Blob` of any config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DynBlob {
+    data: Vec<u8>,
+    encoding: Encoding,
+}
+
+impl DynBlob {
+    /// Create a new empty `DynBlob` with the given encoding
+    #[inline]
+    pub fn new(encoding: Encoding) -> DynBlob {
+        DynBlob::from_vec(Vec::new(), encoding)
+    }
+
+    /// Create a `DynBlob` from an underlying `Vec` and an encoding
+    #[inline]
+    pub fn from_vec(data: Vec<u8>, encoding: Encoding) -> DynBlob {
+        DynBlob { data, encoding }
+    }
+
+    /// Create a `DynBlob` from a `Blob` of any config, using the given encoding
+    #[inline]
+    pub fn from_blob<C: Config>(blob: Blob<C>, encoding: Encoding) -> DynBlob {
+        DynBlob::from_vec(blob.into_vec(), encoding)
+    }
+
+    /// Returns the encoding of the `DynBlob`
+    #[inline]
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Changes the encoding of the `DynBlob`
+    #[inline]
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+
+    /// Use a different encoding for the `DynBlob`
+    #[inline]
+    pub fn with_encoding(self, encoding: Encoding) -> DynBlob {
+        DynBlob::from_vec(self.data, encoding)
+    }
+
+    /// Encode the `DynBlob` to a string, using its encoding
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        self.encoding.encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Decode data of the given encoding into a `DynBlob`
+    pub fn decode_base64<T>(encoded: T, encoding: Encoding) -> Result<DynBlob, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        DynBlob::decode_using(encoded.as_ref(), encoding, |input, output| {
+            encoding.decode(input, output)
+        })
+    }
+
+    /// Decode data of the given encoding into a `DynBlob`, skipping any ASCII whitespace
+    pub fn decode_base64_lenient<T>(encoded: T, encoding: Encoding) -> Result<DynBlob, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        DynBlob::decode_using(encoded.as_ref(), encoding, |input, output| {
+            encoding.decode_lenient(input, output)
+        })
+    }
+
+    /// Checks that data of the given encoding is well-formed, returning its decoded length
+    pub fn validate<T>(encoded: T, encoding: Encoding) -> Result<usize, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        encoding
+            .validate(encoded)
+            .map_err(|err| Error::new(err, encoding.type_name(), encoded.len()))
+    }
+
+    /// Decodes `encoded` into a new `DynBlob` with `decode`, attributing errors to `encoding`
+    fn decode_using<F>(encoded: &[u8], encoding: Encoding, decode: F) -> Result<DynBlob, Error>
+    where
+        F: FnOnce(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+    {
+        let mut blob = DynBlob::new(encoding);
+
+        decode(encoded, &mut blob.data)
+            .map(|_| blob)
+            .map_err(|err| Error::new(err, encoding.type_name(), encoded.len()))
+    }
+
+    /// Convert the `DynBlob` into a `Blob` of any config, discarding its encoding
+    #[inline]
+    pub fn into_blob<C: Config>(self) -> Blob<C> {
+        Blob::from_vec(self.data)
+    }
+
+    /// Consume self and return the inner `Vec<u8>`
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl Display for DynBlob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.encoding.fmt(&self.data, f)
+    }
+}
+
+impl Deref for DynBlob {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl DerefMut for DynBlob {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl AsRef<[u8]> for DynBlob {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AsMut<[u8]> for DynBlob {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl From<DynBlob> for Vec<u8> {
+    #[inline(always)]
+    fn from(blob: DynBlob) -> Vec<u8> {
+        blob.data
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DynBlob {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_bytes(&self.data)
+        }
+    }
+}
+
+/// Visitor decoding a `DynBlob` of the given encoding
+#[cfg(feature = "serde")]
+struct DynBlobVisitor(Encoding);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for DynBlobVisitor {
+    type Value = DynBlob;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} encoded string or byte sequence", self.0.name())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let (offset, trimmed) = whitespace::trim(value);
+
+        // offsets are reported relative to the untrimmed string
+        DynBlob::decode_base64(trimmed, self.0)
+            .map_err(|err| E::custom(err.within(offset, value.len())))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(DynBlob::from_vec(value.to_owned(), self.0))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(DynBlob::from_vec(value, self.0))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        // Preallocate the bytes vec if possible, but remain conservative
+        let mut bytes = Vec::with_capacity(visitor.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(SeqByte(byte)) = visitor.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(DynBlob::from_vec(bytes, self.0))
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        serde::de::Visitor::visit_map(BlobVisitor::<Standard>::new(false), visitor)
+            .map(|blob| DynBlob::from_blob(blob, self.0))
+    }
+}
+
+/// Deserializes a `DynBlob` of this encoding, as the encoding is not part of the serialized form
+#[cfg(feature = "serde")]
+impl<'de> serde::de::DeserializeSeed<'de> for Encoding {
+    type Value = DynBlob;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(DynBlobVisitor(self))
+        } else {
+            deserializer.deserialize_bytes(DynBlobVisitor(self))
+        }
+    }
+}
+
+/// Deserializes a `DynBlob` as standard base-64
+///
+/// Use an `Encoding` as a `DeserializeSeed` to deserialize other encodings.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DynBlob {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::de::DeserializeSeed::deserialize(Encoding::Standard, deserializer)
+    }
+}
@@ -0,0 +1,234 @@
+//! Errors returned when decoding or encoding a `Blob`
+
+use core::error;
+use core::fmt::{self, Display};
+
+use base64::DecodeError;
+
+/// The kind of failure that caused an [`Error`](struct.Error.html)
+///
+/// More kinds may be added in future releases, so matches need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A byte outside of the encoding's alphabet was found
+    InvalidByte,
+    /// The last symbol had non-zero trailing bits, or was otherwise not canonical
+    InvalidLastSymbol,
+    /// The input length is not valid for the encoding
+    InvalidLength,
+    /// The padding was missing, malformed or not allowed
+    InvalidPadding,
+    /// The decoded data would exceed the maximum length of the config
+    TooLong,
+    /// The input was not the canonical encoding of its data, as required by the config
+    NonCanonical,
+    /// The decoded data did not have the exact length required, as by `FixedBlob`
+    WrongLength,
+    /// The output buffer was too short for the encoded data, as with `Blob::encode_to_slice`
+    BufferTooSmall,
+}
+
+/// Error returned when decoding data into a `Blob`, or encoding it into a buffer
+///
+/// Along with the kind of failure, this records where it happened, the offending byte,
+/// the config used to decode, and the length of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    offset: Option<usize>,
+    byte: Option<u8>,
+    max_len: Option<usize>,
+    expected_len: Option<usize>,
+    config: &'static str,
+    input_len: usize,
+}
+
+impl Error {
+    /// Wraps a decode failure from `config` on input of length `input_len`
+    pub(crate) fn new(err: DecodeError, config: &'static str, input_len: usize) -> Error {
+        let (kind, offset, byte) = match err {
+            DecodeError::InvalidByte(i, c) => (ErrorKind::InvalidByte, Some(i), Some(c)),
+            DecodeError::InvalidLastSymbol(i, c) => {
+                (ErrorKind::InvalidLastSymbol, Some(i), Some(c))
+            }
+            DecodeError::InvalidLength(_) => (ErrorKind::InvalidLength, None, None),
+            DecodeError::InvalidPadding => (ErrorKind::InvalidPadding, None, None),
+        };
+
+        Error {
+            kind,
+            offset,
+            byte,
+            max_len: None,
+            expected_len: None,
+            config,
+            input_len,
+        }
+    }
+
+    /// Error for input from `config` that would decode to more than `max_len` bytes
+    pub(crate) fn too_long(max_len: usize, config: &'static str, input_len: usize) -> Error {
+        Error {
+            kind: ErrorKind::TooLong,
+            offset: None,
+            byte: None,
+            max_len: Some(max_len),
+            expected_len: None,
+            config,
+            input_len,
+        }
+    }
+
+    /// Error for input from `config` that did not decode to exactly `expected_len` bytes
+    pub(crate) fn wrong_length(
+        expected_len: usize,
+        config: &'static str,
+        input_len: usize,
+    ) -> Error {
+        Error {
+            kind: ErrorKind::WrongLength,
+            offset: None,
+            byte: None,
+            max_len: None,
+            expected_len: Some(expected_len),
+            config,
+            input_len,
+        }
+    }
+
+    /// Error for encoding `input_len` bytes with `config` into a buffer shorter than
+    /// `expected_len`
+    pub(crate) fn buffer_too_small(
+        expected_len: usize,
+        config: &'static str,
+        input_len: usize,
+    ) -> Error {
+        Error {
+            kind: ErrorKind::BufferTooSmall,
+            offset: None,
+            byte: None,
+            max_len: None,
+            expected_len: Some(expected_len),
+            config,
+            input_len,
+        }
+    }
+
+    /// Error for input from `config` that differs from its canonical encoding at `offset`
+    pub(crate) fn non_canonical(
+        offset: Option<usize>,
+        byte: Option<u8>,
+        config: &'static str,
+        input_len: usize,
+    ) -> Error {
+        Error {
+            kind: ErrorKind::NonCanonical,
+            offset,
+            byte,
+            max_len: None,
+            expected_len: None,
+            config,
+            input_len,
+        }
+    }
+
+    /// Adjusts an error for input that was a slice at `offset` of a longer input
+    pub(crate) fn within(mut self, offset: usize, input_len: usize) -> Error {
+        self.offset = self.offset.map(|i| i + offset);
+        self.input_len = input_len;
+        self
+    }
+
+    /// Returns the kind of failure
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset into the input where decoding failed, if known
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Returns the offending byte of the input, if any
+    #[inline]
+    pub fn byte(&self) -> Option<u8> {
+        self.byte
+    }
+
+    /// Returns the maximum decoded length that was exceeded, for `ErrorKind::TooLong`
+    #[inline]
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Returns the exact decoded length that was required, for `ErrorKind::WrongLength`,
+    /// or the encoded length, for `ErrorKind::BufferTooSmall`
+    #[inline]
+    pub fn expected_len(&self) -> Option<usize> {
+        self.expected_len
+    }
+
+    /// Returns the name of the config used to decode
+    #[inline]
+    pub fn config(&self) -> &'static str {
+        self.config
+    }
+
+    /// Returns the length of the input, in bytes
+    #[inline]
+    pub fn input_len(&self) -> usize {
+        self.input_len
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ErrorKind::InvalidByte => f.write_str("Invalid byte")?,
+            ErrorKind::InvalidLastSymbol => f.write_str("Invalid last symbol")?,
+            ErrorKind::InvalidLength => f.write_str("Invalid length")?,
+            ErrorKind::InvalidPadding => f.write_str("Invalid padding")?,
+            ErrorKind::NonCanonical => f.write_str("Non-canonical encoding")?,
+            ErrorKind::TooLong => write!(
+                f,
+                "Decoded length exceeds {} bytes",
+                self.max_len.unwrap_or(0)
+            )?,
+            ErrorKind::WrongLength => write!(
+                f,
+                "Decoded length is not {} bytes",
+                self.expected_len.unwrap_or(0)
+            )?,
+            ErrorKind::BufferTooSmall => {
+                return write!(
+                    f,
+                    "Output buffer is shorter than {} bytes when encoding {} bytes with {}",
+                    self.expected_len.unwrap_or(0),
+                    self.input_len,
+                    self.config
+                );
+            }
+        }
+
+        match self.byte {
+            Some(c) if c.is_ascii_graphic() => write!(f, " '{}'", c as char)?,
+            Some(c) => write!(f, " {:#04x}", c)?,
+            None => {}
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+
+        write!(
+            f,
+            " when decoding {} bytes with {}",
+            self.input_len, self.config
+        )
+    }
+}
+
+impl error::Error for Error {}
@@ -0,0 +1,134 @@
+//! MongoDB Extended JSON representation of binary data
+
+use alloc_crate::string::String;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde_crate::de::{self, Error, Unexpected};
+use serde_crate::ser::{Serialize, SerializeMap, Serializer};
+
+use super::{check_raw_len, Blob, Config, Standard};
+
+/// Deserializes the value of a `$binary` key, either `{"base64":"...","subType":"00"}`
+/// or the legacy bare string
+pub(crate) struct BinarySeed<C: Config> {
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> BinarySeed<C> {
+    #[inline]
+    pub(crate) fn new() -> BinarySeed<C> {
+        BinarySeed {
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'de, C: Config> de::DeserializeSeed<'de> for BinarySeed<C> {
+    type Value = Blob<C>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, C: Config> de::Visitor<'de> for BinarySeed<C> {
+    type Value = Blob<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Extended JSON binary value")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        decode(value)
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut subtype = false;
+
+        while let Some(key) = visitor.next_key::<String>()? {
+            match key.as_str() {
+                "base64" if data.is_some() => return Err(V::Error::duplicate_field("base64")),
+                "subType" if subtype => return Err(V::Error::duplicate_field("subType")),
+                "base64" => data = Some(decode(&visitor.next_value::<String>()?)?),
+                "subType" => {
+                    check_subtype(&visitor.next_value::<String>()?)?;
+                    subtype = true;
+                }
+                _ => return Err(V::Error::unknown_field(&key, &["base64", "subType"])),
+            }
+        }
+
+        if !subtype {
+            return Err(V::Error::missing_field("subType"));
+        }
+
+        data.ok_or_else(|| V::Error::missing_field("base64"))
+    }
+}
+
+/// Decodes the standard base-64 payload of a `$binary` value, whatever `C` is
+fn decode<C: Config, E: Error>(encoded: &str) -> Result<Blob<C>, E> {
+    let blob = Blob::<Standard>::decode_base64(encoded).map_err(E::custom)?;
+
+    check_raw_len::<C, E>(blob.len())?;
+
+    Ok(Blob::from_vec(blob.into_vec()))
+}
+
+/// Checks that a binary subtype is one or two hexadecimal digits
+pub(crate) fn check_subtype<E: Error>(subtype: &str) -> Result<(), E> {
+    let valid = (1..=2).contains(&subtype.len()) && subtype.bytes().all(|c| c.is_ascii_hexdigit());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(E::invalid_value(
+            Unexpected::Str(subtype),
+            &"a hexadecimal binary subtype",
+        ))
+    }
+}
+
+/// Serializes bytes as canonical Extended JSON, `{"$binary":{"base64":"...","subType":"00"}}`
+pub(crate) struct Binary<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Serialize for Binary<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("$binary", &BinaryValue(self.0))?;
+        map.end()
+    }
+}
+
+struct BinaryValue<'a>(&'a [u8]);
+
+impl<'a> Serialize for BinaryValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut encoded = String::new();
+
+        Standard::encode(self.0, &mut encoded);
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("base64", &encoded)?;
+        map.serialize_entry("subType", "00")?;
+        map.end()
+    }
+}
@@ -0,0 +1,188 @@
+//! Axum extractors and responses for blobs
+
+use alloc_crate::boxed::Box;
+use alloc_crate::string::ToString;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+
+use super::{Blob, Config, Error, Standard};
+
+/// Extractor decoding a base-64 (or `C`-encoded) request body into a `Blob`
+///
+/// The body is read as the `Bytes` extractor reads it, so `DefaultBodyLimit` applies, and then
+/// decoded as `Blob::decode_base64_lenient` would, so a trailing newline is accepted. For base-64
+/// fields within a JSON or form body, use a `Blob` field directly with `Json` or `Form`.
+///
+/// As a response, it sends the encoded text as `text/plain`.
+pub struct Base64Body<C: Config = Standard>(pub Blob<C>);
+
+impl<C: Config> Clone for Base64Body<C> {
+    #[inline]
+    fn clone(&self) -> Base64Body<C> {
+        Base64Body(self.0.clone())
+    }
+}
+
+impl<C: Config> fmt::Debug for Base64Body<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Base64Body").field(&self.0).finish()
+    }
+}
+
+impl<C: Config> PartialEq for Base64Body<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: Config> Eq for Base64Body<C> {}
+
+impl<C: Config> Deref for Base64Body<C> {
+    type Target = Blob<C>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blob<C> {
+        &self.0
+    }
+}
+
+impl<C: Config> DerefMut for Base64Body<C> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Blob<C> {
+        &mut self.0
+    }
+}
+
+impl<C: Config> From<Blob<C>> for Base64Body<C> {
+    #[inline(always)]
+    fn from(blob: Blob<C>) -> Base64Body<C> {
+        Base64Body(blob)
+    }
+}
+
+impl<C: Config> From<Base64Body<C>> for Blob<C> {
+    #[inline(always)]
+    fn from(body: Base64Body<C>) -> Blob<C> {
+        body.0
+    }
+}
+
+/// Rejection returned when a `Base64Body` could not be extracted
+#[derive(Debug)]
+pub enum Base64BodyRejection {
+    /// The body could not be read, as with the `Bytes` extractor
+    Body(BytesRejection),
+    /// The body was not valid base-64, which is rejected with `400 Bad Request`
+    Decode(Error),
+}
+
+impl Display for Base64BodyRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Base64BodyRejection::Body(ref err) => err.fmt(f),
+            Base64BodyRejection::Decode(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl StdError for Base64BodyRejection {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Base64BodyRejection::Body(ref err) => Some(err),
+            Base64BodyRejection::Decode(ref err) => Some(err),
+        }
+    }
+}
+
+impl From<BytesRejection> for Base64BodyRejection {
+    #[inline]
+    fn from(err: BytesRejection) -> Base64BodyRejection {
+        Base64BodyRejection::Body(err)
+    }
+}
+
+impl From<Error> for Base64BodyRejection {
+    #[inline]
+    fn from(err: Error) -> Base64BodyRejection {
+        Base64BodyRejection::Decode(err)
+    }
+}
+
+impl IntoResponse for Base64BodyRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Base64BodyRejection::Body(err) => err.into_response(),
+            Base64BodyRejection::Decode(err) => {
+                (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+            }
+        }
+    }
+}
+
+/// Future reading and decoding a `Base64Body`
+struct FromBody<'a, C: Config> {
+    bytes: Pin<Box<dyn Future<Output = Result<Bytes, BytesRejection>> + Send + 'a>>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'a, C: Config> Future for FromBody<'a, C> {
+    type Output = Result<Base64Body<C>, Base64BodyRejection>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.bytes.as_mut().poll(cx).map(|bytes| {
+            let blob = Blob::decode_base64_lenient(bytes?)?;
+
+            Ok(Base64Body(blob))
+        })
+    }
+}
+
+impl<S: Send + Sync, C: Config> FromRequest<S> for Base64Body<C> {
+    type Rejection = Base64BodyRejection;
+
+    fn from_request(
+        req: Request,
+        state: &S,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> + Send {
+        FromBody {
+            bytes: Box::pin(Bytes::from_request(req, state)),
+            _config: PhantomData,
+        }
+    }
+}
+
+/// Sends the encoded text as `text/plain`
+impl<C: Config> IntoResponse for Base64Body<C> {
+    fn into_response(self) -> Response {
+        let headers = [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; charset=utf-8"),
+        )];
+
+        (headers, self.0.encode_base64()).into_response()
+    }
+}
+
+/// Sends the raw bytes as `application/octet-stream`, without copying them
+impl<C: Config> IntoResponse for Blob<C> {
+    fn into_response(self) -> Response {
+        let headers = [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        )];
+
+        (headers, Bytes::from(self.into_vec())).into_response()
+    }
+}
@@ -0,0 +1,130 @@
+//! C interface
+//!
+//! Every function here is exported unmangled, for C and C++ hosts linking the crate as a static
+//! or dynamic library. Blobs are passed around as opaque `BlobHandle` pointers, which are decoded
+//! with the same strict `Standard` rules as `Blob::decode_base64`, and must be released with
+//! `blob_free`. A matching declaration of the handle in C is just `typedef struct BlobHandle
+//! BlobHandle;`.
+
+use alloc_crate::boxed::Box;
+use core::ptr;
+use core::slice;
+
+use super::{Blob, Standard};
+
+/// Opaque handle owning a `Blob<Standard>`
+pub struct BlobHandle {
+    blob: Blob<Standard>,
+}
+
+/// Borrows `len` bytes at `data`, which may only be null if `len` is zero
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    match len {
+        0 => Some(&[]),
+        _ if data.is_null() => None,
+        _ => Some(slice::from_raw_parts(data, len)),
+    }
+}
+
+/// Moves a blob into a new handle
+fn into_handle(blob: Blob<Standard>) -> *mut BlobHandle {
+    Box::into_raw(Box::new(BlobHandle { blob }))
+}
+
+/// Creates a handle holding a copy of `len` bytes at `data`
+///
+/// Returns null if `data` is null while `len` is not zero.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes, unless `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn blob_new(data: *const u8, len: usize) -> *mut BlobHandle {
+    match bytes(data, len) {
+        Some(bytes) => into_handle(Blob::from(bytes)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Decodes `len` bytes of base-64 text at `input` into a new handle
+///
+/// Returns null if `input` is null while `len` is not zero, or if the text is not valid, padded
+/// and canonical base-64, exactly as `Blob::<Standard>::decode_base64_strict` would reject it.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, unless `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn blob_decode_base64(input: *const u8, len: usize) -> *mut BlobHandle {
+    match bytes(input, len).map(Blob::decode_base64_strict) {
+        Some(Ok(blob)) => into_handle(blob),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Encodes the bytes of `handle` as base-64 into the `cap` bytes at `out`
+///
+/// Returns the length of the encoded text, which is only written if it fits within `cap`, so
+/// calling this with a null `out` and a `cap` of zero returns the length to allocate. The text
+/// is not NUL-terminated. Returns zero if `handle` is null.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, and `out` must point to `cap` writable bytes, unless
+/// `cap` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn blob_encode_base64(
+    handle: *const BlobHandle,
+    out: *mut u8,
+    cap: usize,
+) -> usize {
+    let blob = match handle.as_ref() {
+        Some(handle) => &handle.blob,
+        None => return 0,
+    };
+
+    let len = blob.encoded_len();
+
+    if len > 0 && len <= cap && !out.is_null() {
+        let _ = blob.encode_to_slice(slice::from_raw_parts_mut(out, cap));
+    }
+
+    len
+}
+
+/// Returns the number of bytes held by `handle`, or zero if it is null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn blob_len(handle: *const BlobHandle) -> usize {
+    handle.as_ref().map_or(0, |handle| handle.blob.len())
+}
+
+/// Returns a pointer to the bytes held by `handle`, or null if it is null
+///
+/// The pointer is valid for `blob_len` bytes until the handle is freed. It is not null for an
+/// empty blob, but must not be read from.
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn blob_data(handle: *const BlobHandle) -> *const u8 {
+    handle
+        .as_ref()
+        .map_or(ptr::null(), |handle| handle.blob.as_ptr())
+}
+
+/// Frees `handle`, doing nothing if it is null
+///
+/// # Safety
+///
+/// `handle` must be null or a live handle, which must not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn blob_free(handle: *mut BlobHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
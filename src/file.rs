@@ -0,0 +1,160 @@
+//! Reading and writing the bytes of blobs to files
+
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::vec::Vec;
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::stream::invalid_data;
+use super::Error;
+
+/// I/O error annotated with the path of the file it occurred on
+#[derive(Debug)]
+struct PathError {
+    path: PathBuf,
+    err: io::Error,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.err)
+    }
+}
+
+impl StdError for PathError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.err)
+    }
+}
+
+/// Annotates `err` with `path`, keeping its kind
+fn with_path(path: &Path, err: io::Error) -> io::Error {
+    let kind = err.kind();
+
+    io::Error::new(
+        kind,
+        PathError {
+            path: path.to_owned(),
+            err,
+        },
+    )
+}
+
+/// Returns the error for a file of `len` bytes, if it exceeds `max`
+fn check_file_len(len: u64, max: Option<usize>, config: &'static str) -> io::Result<()> {
+    match max {
+        Some(max) if len > max as u64 => {
+            Err(invalid_data(Error::too_long(max, config, len as usize)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Number of temporary files created by this process, so that concurrent writes never share one
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns a new path for the temporary file an atomic write to `path` goes through
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+
+    let n = TEMP_FILES.fetch_add(1, Ordering::Relaxed);
+
+    path.with_file_name(format!(".{}.{}.{}.tmp", name, process::id(), n))
+}
+
+/// Creates a new temporary file for an atomic write to `path`, never opening an existing one
+fn create_temp(path: &Path) -> io::Result<(PathBuf, File)> {
+    let mut attempts = 0;
+
+    loop {
+        let temp = temp_path(path);
+
+        match OpenOptions::new().write(true).create_new(true).open(&temp) {
+            // left behind by an earlier process with the same id
+            Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists && attempts < 16 => {
+                attempts += 1;
+            }
+            result => return result.map(|file| (temp, file)),
+        }
+    }
+}
+
+/// Syncs the directory containing `path`, so that a rename into it is durable
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    File::open(parent)?.sync_all()
+}
+
+/// Other platforms cannot open a directory to sync it
+#[cfg(not(unix))]
+#[inline]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Reads the whole file at `path`, failing once it exceeds `max` bytes
+///
+/// This is not generic over the config, so that it can run on another thread.
+pub(crate) fn read(path: &Path, max: Option<usize>, config: &'static str) -> io::Result<Vec<u8>> {
+    let read = || {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+
+        check_file_len(len, max, config)?;
+
+        let mut data = Vec::with_capacity(len as usize);
+
+        match max {
+            // the file may grow after its length was checked
+            Some(max) => {
+                file.take(max as u64 + 1).read_to_end(&mut data)?;
+
+                check_file_len(data.len() as u64, Some(max), config)?;
+            }
+            None => {
+                file.read_to_end(&mut data)?;
+            }
+        }
+
+        Ok(data)
+    };
+
+    read().map_err(|err| with_path(path, err))
+}
+
+/// Writes `data` to the file at `path`, through a temporary file renamed into place if `atomic`
+pub(crate) fn write(path: &Path, data: &[u8], atomic: bool) -> io::Result<()> {
+    if !atomic {
+        return fs::write(path, data).map_err(|err| with_path(path, err));
+    }
+
+    let (temp, mut file) = create_temp(path).map_err(|err| with_path(path, err))?;
+
+    let mut write = || {
+        file.write_all(data)?;
+        file.sync_all()?;
+
+        fs::rename(&temp, path)
+    };
+
+    if let Err(err) = write() {
+        let _ = fs::remove_file(&temp);
+
+        return Err(with_path(path, err));
+    }
+
+    sync_parent(path).map_err(|err| with_path(path, err))
+}
@@ -0,0 +1,274 @@
+//! Blobs of a fixed length, stored in an array
+
+use alloc_crate::string::String;
+use core::any;
+use core::convert::TryFrom;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serde::deserialize_as;
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::whitespace;
+use super::{Blob, Config, Error, ErrorKind, Hex};
+
+/// Blob structure holding exactly `N` bytes in a `[u8; N]`
+///
+/// Decoding fails with `ErrorKind::WrongLength` unless the data is exactly `N` bytes long,
+/// so keys, nonces and hashes have their length checked by the type. It encodes, displays
+/// and serializes exactly as a `Blob<C>`.
+pub struct FixedBlob<C: Config, const N: usize> {
+    data: [u8; N],
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config, const N: usize> FixedBlob<C, N> {
+    /// Create a `FixedBlob` from the given array
+    #[inline]
+    pub const fn new(data: [u8; N]) -> FixedBlob<C, N> {
+        FixedBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode a `FixedBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// Input that cannot decode to exactly `N` bytes is rejected before decoding, and configs
+    /// that can decode into a slice, such as base-64 and hex, decode straight into the array.
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<FixedBlob<C, N>, Error> {
+        let encoded = encoded.as_ref();
+        let wrong_length = || Error::wrong_length(N, any::type_name::<C>(), encoded.len());
+
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        match Blob::<C>::check_limit(encoded, N, 0) {
+            Err(ref err) if err.kind() == ErrorKind::TooLong => return Err(wrong_length()),
+            result => result?,
+        }
+
+        if C::decoded_len_bounds(encoded.len()).1 < N {
+            return Err(wrong_length());
+        }
+
+        let mut data = [0; N];
+
+        match Blob::<C>::decode_to_slice(encoded, &mut data) {
+            Some(Ok(len)) if len == N => Ok(FixedBlob::new(data)),
+            Some(Ok(_)) => Err(wrong_length()),
+            Some(Err(err)) => Err(err),
+            None => FixedBlob::from_blob(Blob::decode_base64(encoded)?, encoded.len()),
+        }
+    }
+
+    /// Checks the length of a decoded `Blob`, reporting errors against `input_len`
+    fn from_blob(blob: Blob<C>, input_len: usize) -> Result<FixedBlob<C, N>, Error> {
+        <[u8; N]>::try_from(&blob[..])
+            .map(FixedBlob::new)
+            .map_err(|_| Error::wrong_length(N, any::type_name::<C>(), input_len))
+    }
+
+    /// Encode the `FixedBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Encode the bytes to a lowercase hexadecimal string, regardless of `C`
+    pub fn to_hex(&self) -> String {
+        let mut encoded = String::new();
+
+        Hex::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.data[..])
+    }
+
+    /// Consume self and return the inner array
+    #[inline]
+    pub fn into_array(self) -> [u8; N] {
+        self.data
+    }
+}
+
+impl<C: Config, const N: usize> Default for FixedBlob<C, N> {
+    #[inline]
+    fn default() -> FixedBlob<C, N> {
+        FixedBlob::new([0; N])
+    }
+}
+
+impl<C: Config, const N: usize> Clone for FixedBlob<C, N> {
+    #[inline(always)]
+    fn clone(&self) -> FixedBlob<C, N> {
+        *self
+    }
+}
+
+impl<C: Config, const N: usize> Copy for FixedBlob<C, N> {}
+
+impl<C: Config, const N: usize> fmt::Debug for FixedBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("FixedBlob").field(&&self.data[..]).finish()
+    }
+}
+
+impl<C: Config, const N: usize> Display for FixedBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `FixedBlob` from a string, as `Blob` would
+impl<C: Config, const N: usize> FromStr for FixedBlob<C, N> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, trimmed) = whitespace::trim(s);
+
+        // offsets are reported relative to the untrimmed string
+        FixedBlob::decode_base64(trimmed).map_err(|err| err.within(offset, s.len()))
+    }
+}
+
+impl<C: Config, const N: usize> PartialEq for FixedBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config, const N: usize> Eq for FixedBlob<C, N> {}
+
+impl<C: Config, const N: usize> PartialEq<Blob<C>> for FixedBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.data[..] == other[..]
+    }
+}
+
+/// Hashes the same as the equivalent `Blob<C>`
+impl<C: Config, const N: usize> Hash for FixedBlob<C, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data[..].hash(state);
+    }
+}
+
+impl<C: Config, const N: usize> Deref for FixedBlob<C, N> {
+    type Target = [u8; N];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8; N] {
+        &self.data
+    }
+}
+
+impl<C: Config, const N: usize> DerefMut for FixedBlob<C, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8; N] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, const N: usize> AsRef<[u8]> for FixedBlob<C, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config, const N: usize> AsMut<[u8]> for FixedBlob<C, N> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, const N: usize> From<[u8; N]> for FixedBlob<C, N> {
+    #[inline(always)]
+    fn from(data: [u8; N]) -> FixedBlob<C, N> {
+        FixedBlob::new(data)
+    }
+}
+
+impl<C: Config, const N: usize> From<FixedBlob<C, N>> for [u8; N] {
+    #[inline(always)]
+    fn from(blob: FixedBlob<C, N>) -> [u8; N] {
+        blob.data
+    }
+}
+
+impl<C: Config, const N: usize> From<FixedBlob<C, N>> for Blob<C> {
+    #[inline]
+    fn from(blob: FixedBlob<C, N>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+impl<'a, C: Config, const N: usize> TryFrom<&'a [u8]> for FixedBlob<C, N> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        <[u8; N]>::try_from(bytes)
+            .map(FixedBlob::new)
+            .map_err(|_| Error::wrong_length(N, any::type_name::<C>(), bytes.len()))
+    }
+}
+
+impl<C: Config, const N: usize> TryFrom<Blob<C>> for FixedBlob<C, N> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(blob: Blob<C>) -> Result<Self, Self::Error> {
+        FixedBlob::try_from(&blob[..])
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, const N: usize> serde::Serialize for FixedBlob<C, N> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config, const N: usize> serde::Deserialize<'de> for FixedBlob<C, N> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as::<C, [u8; N], D>(deserializer).map(FixedBlob::new)
+    }
+}
@@ -0,0 +1,101 @@
+//! Wrappers pinning how a `Blob` is serialized, regardless of the format
+
+use core::ops::{Deref, DerefMut};
+
+use serde_crate as serde;
+
+use super::{Blob, BlobVisitor, Config};
+
+/// Wrapper serializing a `Blob` as an encoded string, even in binary formats
+///
+/// The string is encoded and decoded with the config of the `Blob`, as human-readable formats
+/// would, which keeps the serialized form stable across languages. When deserializing, strings
+/// are requested from the format, though anything a `Blob` accepts is still decoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ForceBase64<T>(pub T);
+
+/// Wrapper serializing a `Blob` as raw bytes, even in human-readable formats
+///
+/// Formats without a byte string type, such as JSON, write an array of numbers instead. When
+/// deserializing, bytes are requested from the format, though anything a `Blob` accepts is
+/// still decoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ForceBytes<T>(pub T);
+
+macro_rules! impl_force {
+    ($($name:ident: $serialize:ident, $deserialize:ident;)*) => {
+        $(
+            impl<T> $name<T> {
+                /// Consume self and return the inner value
+                #[inline]
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T> From<T> for $name<T> {
+                #[inline(always)]
+                fn from(value: T) -> $name<T> {
+                    $name(value)
+                }
+            }
+
+            impl<T> Deref for $name<T> {
+                type Target = T;
+
+                #[inline(always)]
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T> DerefMut for $name<T> {
+                #[inline(always)]
+                fn deref_mut(&mut self) -> &mut T {
+                    &mut self.0
+                }
+            }
+
+            impl<C: Config> serde::Serialize for $name<Blob<C>> {
+                #[inline]
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    $serialize(&self.0, serializer)
+                }
+            }
+
+            impl<'de, C: Config> serde::Deserialize<'de> for $name<Blob<C>> {
+                #[inline]
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    deserializer.$deserialize(BlobVisitor::new(false)).map($name)
+                }
+            }
+        )*
+    };
+}
+
+impl_force! {
+    ForceBase64: serialize_base64, deserialize_str;
+    ForceBytes: serialize_raw, deserialize_bytes;
+}
+
+#[inline]
+fn serialize_base64<C: Config, S: serde::Serializer>(
+    blob: &Blob<C>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(blob)
+}
+
+#[inline]
+fn serialize_raw<C: Config, S: serde::Serializer>(
+    blob: &Blob<C>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(blob)
+}
@@ -0,0 +1,290 @@
+//! Blobs caching the hash of their bytes, for use as map keys
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Error, Standard};
+
+/// Marks the cached hash as not yet computed
+const UNHASHED: u64 = 0;
+
+/// Blob structure caching a hash of its bytes the first time it is hashed
+///
+/// `Hash` writes only the cached 64-bit hash, so probing a large `HashMap` or `HashSet` keyed
+/// by multi-kilobyte blobs no longer rehashes the whole payload each time. Any mutation,
+/// including through `DerefMut`, clears the cache. Equality compares cached hashes first when
+/// both are known. Since it hashes differently from `[u8]`, it does not implement
+/// `Borrow<[u8]>`. It encodes, displays and serializes exactly as a `Blob<C>`.
+///
+/// The cache is interior mutability that never changes the hash or equality, so clippy's
+/// `mutable_key_type` lint is a false positive for `HashedBlob` keys.
+pub struct HashedBlob<C: Config = Standard> {
+    data: Vec<u8>,
+    hash: AtomicU64,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> HashedBlob<C> {
+    /// Create a new empty `HashedBlob`
+    #[inline]
+    pub fn new() -> HashedBlob<C> {
+        HashedBlob::from_vec(Vec::new())
+    }
+
+    /// Create a `HashedBlob` from an underlying `Vec`, without hashing it yet
+    #[inline]
+    pub fn from_vec(data: Vec<u8>) -> HashedBlob<C> {
+        HashedBlob {
+            data,
+            hash: AtomicU64::new(UNHASHED),
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode a `HashedBlob` from the given string, as `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<HashedBlob<C>, Error> {
+        Blob::decode_base64(encoded).map(HashedBlob::from)
+    }
+
+    /// Encode the `HashedBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Returns the hash of the bytes, computing and caching it if needed
+    ///
+    /// The hash is the same for equal bytes in any process built with the same Rust release,
+    /// but is not cryptographic.
+    pub fn content_hash(&self) -> u64 {
+        let hash = self.hash.load(Ordering::Relaxed);
+
+        if hash != UNHASHED {
+            return hash;
+        }
+
+        let mut hasher = DefaultHasher::new();
+
+        self.data.hash(&mut hasher);
+
+        // keep the sentinel free, at the cost of one colliding value
+        let hash = hasher.finish().max(UNHASHED + 1);
+
+        self.hash.store(hash, Ordering::Relaxed);
+
+        hash
+    }
+
+    /// Returns `true` if the hash is currently cached
+    #[inline]
+    pub fn is_hashed(&self) -> bool {
+        self.hash.load(Ordering::Relaxed) != UNHASHED
+    }
+
+    /// Returns the bytes for mutation, clearing the cached hash
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut Vec<u8> {
+        *self.hash.get_mut() = UNHASHED;
+
+        &mut self.data
+    }
+
+    /// Appends the given bytes, clearing the cached hash
+    #[inline]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.make_mut().extend_from_slice(bytes);
+    }
+
+    /// Convert into an owned `Blob` without copying
+    #[inline]
+    pub fn into_blob(self) -> Blob<C> {
+        Blob::from_vec(self.data)
+    }
+
+    /// Consume self and return the inner `Vec<u8>`
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl<C: Config> Default for HashedBlob<C> {
+    #[inline]
+    fn default() -> HashedBlob<C> {
+        HashedBlob::new()
+    }
+}
+
+/// Copies the bytes along with any cached hash
+impl<C: Config> Clone for HashedBlob<C> {
+    #[inline]
+    fn clone(&self) -> HashedBlob<C> {
+        HashedBlob {
+            data: self.data.clone(),
+            hash: AtomicU64::new(self.hash.load(Ordering::Relaxed)),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> fmt::Debug for HashedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HashedBlob").field(&&self.data[..]).finish()
+    }
+}
+
+impl<C: Config> Display for HashedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `HashedBlob` from a string, as `Blob` would
+impl<C: Config> FromStr for HashedBlob<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(HashedBlob::from)
+    }
+}
+
+/// Compares cached hashes first when both are known, then the bytes
+impl<C: Config> PartialEq for HashedBlob<C> {
+    fn eq(&self, other: &Self) -> bool {
+        let (a, b) = (
+            self.hash.load(Ordering::Relaxed),
+            other.hash.load(Ordering::Relaxed),
+        );
+
+        if a != UNHASHED && b != UNHASHED && a != b {
+            return false;
+        }
+
+        self.data == other.data
+    }
+}
+
+impl<C: Config> Eq for HashedBlob<C> {}
+
+impl<C: Config> PartialEq<Blob<C>> for HashedBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.data[..] == other[..]
+    }
+}
+
+/// Writes only the cached hash, computing it on first use
+impl<C: Config> Hash for HashedBlob<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.content_hash());
+    }
+}
+
+impl<C: Config> Deref for HashedBlob<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Clears the cached hash, as with `make_mut`
+impl<C: Config> DerefMut for HashedBlob<C> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.make_mut()
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for HashedBlob<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> Extend<u8> for HashedBlob<C> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.make_mut().extend(iter);
+    }
+}
+
+impl<C: Config> From<Vec<u8>> for HashedBlob<C> {
+    #[inline(always)]
+    fn from(data: Vec<u8>) -> HashedBlob<C> {
+        HashedBlob::from_vec(data)
+    }
+}
+
+impl<'a, C: Config> From<&'a [u8]> for HashedBlob<C> {
+    #[inline]
+    fn from(data: &'a [u8]) -> HashedBlob<C> {
+        HashedBlob::from_vec(data.to_vec())
+    }
+}
+
+/// Takes ownership of the `Vec<u8>` without copying
+impl<C: Config> From<Blob<C>> for HashedBlob<C> {
+    #[inline]
+    fn from(blob: Blob<C>) -> HashedBlob<C> {
+        HashedBlob::from_vec(blob.into_vec())
+    }
+}
+
+impl<C: Config> From<HashedBlob<C>> for Blob<C> {
+    #[inline]
+    fn from(blob: HashedBlob<C>) -> Blob<C> {
+        blob.into_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for HashedBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for HashedBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(HashedBlob::from)
+    }
+}
@@ -0,0 +1,290 @@
+//! Blobs stored in a `heapless::Vec`, for targets without a heap
+
+use alloc_crate::string::String;
+#[cfg(feature = "serde")]
+use alloc_crate::vec::Vec;
+use core::any;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+use heapless::Vec as HeaplessVec;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serde_impl::{deserialize_inline, InlineBlob};
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{whitespace, Blob, Config, Error};
+
+/// Blob structure holding up to `N` bytes in a `heapless::Vec<u8, N>`
+///
+/// The bytes never live on the heap, so blobs can be kept in statics or on the stack of
+/// bare-metal firmware. Decoding fails with `ErrorKind::TooLong`, before decoding anything, if
+/// the data would exceed `N` bytes. The base-64 and hex configs decode straight into the
+/// `heapless::Vec`, and `encode_to_slice` encodes into a fixed buffer without allocating for
+/// them. Other configs decode text through a temporary buffer, while binary formats such as
+/// postcard deserialize without one. It encodes, displays and serializes exactly as a
+/// `Blob<C>`.
+pub struct HeaplessBlob<C: Config, const N: usize> {
+    data: HeaplessVec<u8, N>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config, const N: usize> HeaplessBlob<C, N> {
+    /// Create a new empty `HeaplessBlob`
+    #[inline]
+    pub const fn new() -> HeaplessBlob<C, N> {
+        HeaplessBlob::from_vec(HeaplessVec::new())
+    }
+
+    /// Create a `HeaplessBlob` from an underlying `heapless::Vec`
+    #[inline]
+    pub const fn from_vec(data: HeaplessVec<u8, N>) -> HeaplessBlob<C, N> {
+        HeaplessBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+
+    /// Create a `HeaplessBlob` holding a copy of the given bytes
+    ///
+    /// Fails with `ErrorKind::TooLong` if there are more than `N` bytes.
+    pub fn from_slice(bytes: &[u8]) -> Result<HeaplessBlob<C, N>, Error> {
+        HeaplessVec::from_slice(bytes)
+            .map(HeaplessBlob::from_vec)
+            .map_err(|_| Error::too_long(N, any::type_name::<C>(), bytes.len()))
+    }
+
+    /// Decode a `HeaplessBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// Fails with `ErrorKind::TooLong` if the data would exceed `N` bytes.
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<HeaplessBlob<C, N>, Error> {
+        let encoded = encoded.as_ref();
+
+        Blob::<C>::check_limit(encoded, N, 0)?;
+
+        let mut data = HeaplessVec::new();
+
+        // always fits, as the capacity is `N`
+        let _ = data.resize(N, 0);
+
+        match Blob::<C>::decode_to_slice(encoded, &mut data) {
+            Some(len) => {
+                data.truncate(len?);
+
+                Ok(HeaplessBlob::from_vec(data))
+            }
+            None => HeaplessBlob::from_blob(Blob::decode_base64(encoded)?, encoded.len()),
+        }
+    }
+
+    /// Checks the length of a decoded `Blob`, reporting errors against `input_len`
+    fn from_blob(blob: Blob<C>, input_len: usize) -> Result<HeaplessBlob<C, N>, Error> {
+        HeaplessVec::from_slice(&blob)
+            .map(HeaplessBlob::from_vec)
+            .map_err(|_| Error::too_long(N, any::type_name::<C>(), input_len))
+    }
+
+    /// Encode the `HeaplessBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Returns the length of the encoded form, as `Blob::encoded_len` does
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        C::encoded_len(&self.data)
+    }
+
+    /// Encode the bytes into the start of `output`, returning the encoded length, as
+    /// `Blob::encode_to_slice` does
+    pub fn encode_to_slice(&self, output: &mut [u8]) -> Result<usize, Error> {
+        C::encode_to_slice(&self.data, output).ok_or_else(|| {
+            Error::buffer_too_small(self.encoded_len(), any::type_name::<C>(), self.len())
+        })
+    }
+
+    /// Returns the maximum number of bytes, `N`
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Appends the given bytes, failing with `ErrorKind::TooLong` and leaving the blob
+    /// unchanged if they do not fit
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let len = self.len() + bytes.len();
+
+        self.data
+            .extend_from_slice(bytes)
+            .map_err(|_| Error::too_long(N, any::type_name::<C>(), len))
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.data[..])
+    }
+
+    /// Consume self and return the inner `heapless::Vec`
+    #[inline]
+    pub fn into_vec(self) -> HeaplessVec<u8, N> {
+        self.data
+    }
+}
+
+impl<C: Config, const N: usize> Default for HeaplessBlob<C, N> {
+    #[inline]
+    fn default() -> HeaplessBlob<C, N> {
+        HeaplessBlob::new()
+    }
+}
+
+impl<C: Config, const N: usize> Clone for HeaplessBlob<C, N> {
+    #[inline]
+    fn clone(&self) -> HeaplessBlob<C, N> {
+        HeaplessBlob::from_vec(self.data.clone())
+    }
+}
+
+impl<C: Config, const N: usize> fmt::Debug for HeaplessBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("HeaplessBlob")
+            .field(&&self.data[..])
+            .finish()
+    }
+}
+
+impl<C: Config, const N: usize> Display for HeaplessBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `HeaplessBlob` from a string, as `Blob` would
+impl<C: Config, const N: usize> FromStr for HeaplessBlob<C, N> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, trimmed) = whitespace::trim(s);
+
+        // offsets are reported relative to the untrimmed string
+        HeaplessBlob::decode_base64(trimmed).map_err(|err| err.within(offset, s.len()))
+    }
+}
+
+impl<C: Config, const N: usize> PartialEq for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config, const N: usize> Eq for HeaplessBlob<C, N> {}
+
+impl<C: Config, const N: usize> PartialEq<Blob<C>> for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.data[..] == other[..]
+    }
+}
+
+/// Hashes the same as the equivalent `Blob<C>`
+impl<C: Config, const N: usize> Hash for HeaplessBlob<C, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data[..].hash(state);
+    }
+}
+
+impl<C: Config, const N: usize> Deref for HeaplessBlob<C, N> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config, const N: usize> DerefMut for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, const N: usize> AsRef<[u8]> for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config, const N: usize> AsMut<[u8]> for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+}
+
+impl<C: Config, const N: usize> From<HeaplessVec<u8, N>> for HeaplessBlob<C, N> {
+    #[inline(always)]
+    fn from(data: HeaplessVec<u8, N>) -> HeaplessBlob<C, N> {
+        HeaplessBlob::from_vec(data)
+    }
+}
+
+impl<C: Config, const N: usize> From<HeaplessBlob<C, N>> for Blob<C> {
+    #[inline]
+    fn from(blob: HeaplessBlob<C, N>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, const N: usize> serde::Serialize for HeaplessBlob<C, N> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+/// Borrows the bytes from binary formats such as postcard, copying only into the blob itself
+#[cfg(feature = "serde")]
+impl<'de, C: Config, const N: usize> serde::Deserialize<'de> for HeaplessBlob<C, N> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_inline::<C, HeaplessBlob<C, N>, D, N>(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, const N: usize> InlineBlob<C, N> for HeaplessBlob<C, N> {
+    const SPILL: bool = false;
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<HeaplessBlob<C, N>, Error> {
+        HeaplessBlob::from_slice(bytes)
+    }
+
+    #[inline]
+    fn from_byte_vec(bytes: Vec<u8>) -> Result<HeaplessBlob<C, N>, Error> {
+        HeaplessBlob::from_slice(&bytes)
+    }
+}
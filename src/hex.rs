@@ -0,0 +1,123 @@
+//! Hexadecimal encoding and decoding
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::fmt;
+use core::str;
+
+use base64::DecodeError;
+
+pub const LOWER: &[u8; 16] = b"0123456789abcdef";
+pub const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+
+#[inline]
+fn encode_byte(byte: u8, table: &[u8; 16]) -> [u8; 2] {
+    [table[(byte >> 4) as usize], table[(byte & 0xf) as usize]]
+}
+
+/// Appends the hex encoding of `input` to `output`
+pub fn encode(input: &[u8], table: &[u8; 16], output: &mut String) {
+    output.reserve(input.len() * 2);
+
+    for &byte in input {
+        for &c in &encode_byte(byte, table) {
+            output.push(c as char);
+        }
+    }
+}
+
+/// Writes the hex encoding of `input` to the start of `output`, if it is long enough
+pub fn encode_to_slice(input: &[u8], table: &[u8; 16], output: &mut [u8]) -> Option<usize> {
+    let len = input.len().checked_mul(2)?;
+
+    if output.len() < len {
+        return None;
+    }
+
+    for (out, &byte) in output.chunks_mut(2).zip(input) {
+        out.copy_from_slice(&encode_byte(byte, table));
+    }
+
+    Some(len)
+}
+
+/// Writes the hex encoding of `input` to a formatter, using a small stack buffer
+pub fn fmt(input: &[u8], table: &[u8; 16], f: &mut fmt::Formatter) -> fmt::Result {
+    let mut buf = [0u8; 128];
+
+    for chunk in input.chunks(buf.len() / 2) {
+        for (out, &byte) in buf.chunks_mut(2).zip(chunk) {
+            out.copy_from_slice(&encode_byte(byte, table));
+        }
+
+        // hex digits are always ASCII
+        f.write_str(str::from_utf8(&buf[..chunk.len() * 2]).map_err(|_| fmt::Error)?)?;
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn decode_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decodes case-insensitive hex from `input`, appending the bytes to `output`
+pub fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+    if !input.len().is_multiple_of(2) {
+        return Err(DecodeError::InvalidLength(input.len()));
+    }
+
+    output.reserve(input.len() / 2);
+
+    for (i, pair) in input.chunks(2).enumerate() {
+        let hi = decode_nibble(pair[0]).ok_or(DecodeError::InvalidByte(i * 2, pair[0]))?;
+        let lo = decode_nibble(pair[1]).ok_or(DecodeError::InvalidByte(i * 2 + 1, pair[1]))?;
+
+        output.push(hi << 4 | lo);
+    }
+
+    Ok(())
+}
+
+/// Decodes case-insensitive hex from `input` into the start of `output`, returning the decoded
+/// length, or `None` if `output` is too short
+pub fn decode_to_slice(input: &[u8], output: &mut [u8]) -> Option<Result<usize, DecodeError>> {
+    if !input.len().is_multiple_of(2) {
+        return Some(Err(DecodeError::InvalidLength(input.len())));
+    }
+
+    let output = output.get_mut(..input.len() / 2)?;
+
+    for (i, (pair, byte)) in input.chunks(2).zip(output).enumerate() {
+        let hi = match decode_nibble(pair[0]) {
+            Some(hi) => hi,
+            None => return Some(Err(DecodeError::InvalidByte(i * 2, pair[0]))),
+        };
+        let lo = match decode_nibble(pair[1]) {
+            Some(lo) => lo,
+            None => return Some(Err(DecodeError::InvalidByte(i * 2 + 1, pair[1]))),
+        };
+
+        *byte = hi << 4 | lo;
+    }
+
+    Some(Ok(input.len() / 2))
+}
+
+/// Validates case-insensitive hex from `input`, returning the decoded length
+pub fn validate(input: &[u8]) -> Result<usize, DecodeError> {
+    if !input.len().is_multiple_of(2) {
+        return Err(DecodeError::InvalidLength(input.len()));
+    }
+
+    match input.iter().position(|&c| decode_nibble(c).is_none()) {
+        Some(i) => Err(DecodeError::InvalidByte(i, input[i])),
+        None => Ok(input.len() / 2),
+    }
+}
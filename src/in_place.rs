@@ -0,0 +1,106 @@
+//! Decoding into the front of the buffer holding the encoded input
+
+use alloc_crate::vec::Vec;
+use core::any;
+use core::ops::Range;
+
+use base64::DecodeError;
+
+use super::whitespace::shift_offsets;
+use super::{Blob, Config, Error};
+
+/// Decoder of a piece of input, such as `Config::decode`
+pub(crate) type DecodeFn = fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>;
+
+/// Number of symbols decoded at a time, a multiple of the base-64, base-32 and hex quanta
+const CHUNK_LEN: usize = 1024;
+
+/// Decodes `buf` as `Blob::decode_base64` would, reusing it for the decoded bytes
+///
+/// Configs without an `ENCODED_BLOCK_LEN`, and input with whitespace, decode into a new buffer
+/// instead.
+pub(crate) fn decode<C: Config>(mut buf: Vec<u8>) -> Result<Blob<C>, Error> {
+    match decode_front::<C>(&mut buf) {
+        Some(written) => {
+            buf.truncate(written?);
+
+            Ok(Blob::from_vec(buf))
+        }
+        None => Blob::decode_base64(buf),
+    }
+}
+
+/// Decodes `buf` as `Blob::decode_base64` would, writing the bytes over its front and returning
+/// their length, or `None` for configs without an `ENCODED_BLOCK_LEN` and input with whitespace
+///
+/// Each chunk of input is decoded into a small scratch buffer and then copied to the front of
+/// `buf`, which never overtakes the input still to be decoded.
+pub(crate) fn decode_front<C: Config>(buf: &mut [u8]) -> Option<Result<usize, Error>> {
+    let quantum = match C::ENCODED_BLOCK_LEN {
+        Some(quantum) if !buf.iter().any(u8::is_ascii_whitespace) => quantum,
+        _ => return None,
+    };
+
+    let input_len = buf.len();
+    let chunk_len = (CHUNK_LEN / quantum).max(1) * quantum;
+
+    let mut scratch = Vec::with_capacity(chunk_len);
+    let mut read = 0;
+    let mut written = 0;
+
+    while read < input_len {
+        let end = input_len.min(read + chunk_len);
+
+        scratch.clear();
+
+        if let Err(err) = decode_chunk::<C>(buf, read..end, &mut scratch, C::decode) {
+            return Some(Err(err));
+        }
+
+        if let Some(max) = C::MAX_DECODED_LEN {
+            if written + scratch.len() > max {
+                return Some(Err(Error::too_long(max, any::type_name::<C>(), input_len)));
+            }
+        }
+
+        // decoded bytes are always fewer than the symbols they came from
+        buf[written..written + scratch.len()].copy_from_slice(&scratch);
+
+        written += scratch.len();
+        read = end;
+    }
+
+    Some(Ok(written))
+}
+
+/// Decodes `input[range]`, which must start and end on block boundaries, as one piece of
+/// decoding all of `input` with `decode`, such as `C::decode`, appending the bytes to `output`
+///
+/// Padding is only accepted at the end of `input`, and errors are reported against all of it.
+pub(crate) fn decode_chunk<C: Config>(
+    input: &[u8],
+    range: Range<usize>,
+    output: &mut Vec<u8>,
+    decode: DecodeFn,
+) -> Result<(), Error> {
+    let config = any::type_name::<C>();
+    let start = range.start;
+    let last = range.end == input.len();
+    let chunk = &input[range];
+
+    if !last {
+        if let Some(j) = chunk.iter().position(|&c| c == b'=') {
+            let err = DecodeError::InvalidByte(start + j, b'=');
+
+            return Err(Error::new(err, config, input.len()));
+        }
+    }
+
+    let len = output.len();
+
+    decode(chunk, output)
+        .map_err(|err| Error::new(shift_offsets(err, start), config, input.len()))?;
+
+    Blob::<C>::check_canonical(chunk, &output[len..], false)
+        .map_err(|err| err.within(start, input.len()))
+}
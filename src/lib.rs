@@ -4,8 +4,35 @@
 //! encoding and decoding to/from base-64, with support for type-level encoding
 //! configurations suitable for url-safe base-64.
 //!
+//! Hexadecimal configs, `Hex` and `HexUpper`, RFC 4648 base-32 configs, such as
+//! `Base32`, `Base32Hex` and `Crockford`, as well as `Base58` and `Ascii85`, are also
+//! available for when a `Blob` should be represented in those encodings instead. Z85 can only
+//! encode whole 4-byte groups, so it is available through `encode_z85` and `decode_z85`.
+//! When the encoding is only known at runtime, `DynBlob` carries an `Encoding` alongside
+//! the bytes instead.
+//!
 //! When serializing, it will encode the binary data as base-64, and when deserializing it
-//! can either read and decode a base-64 encoded string or a raw sequence of bytes.
+//! can either read and decode a base-64 encoded string or a raw sequence of bytes from 0 to
+//! 255. `LenientBlob` also accepts signed bytes, as produced for Java's `byte[]`.
+//! Binary formats that are not human-readable, such as bincode or CBOR, store the raw
+//! bytes instead, and `CowBlob` can borrow them from the input rather than copying.
+//! The `{"type":"Buffer","data":[...]}` form produced by Node.js, and the MongoDB Extended
+//! JSON `{"$binary":{"base64":"...","subType":"00"}}` form, are also accepted.
+//! Plain `Vec<u8>` or `[u8; N]` fields can use the same encodings through the helper
+//! modules in `blob::serde`, such as `#[serde(with = "blob::serde::base64")]`.
+//!
+//! The crate is `no_std` with `alloc` when the default `std` feature is disabled. Encoding,
+//! decoding, `Display`, `FromStr` and serde support remain available, while `io` readers and
+//! writers, `encode_to`, files and `HashedBlob` require `std`.
+//!
+//! Serde support is behind the default `serde` feature. Without it, the crate does not depend on
+//! serde at all, and `LenientBlob`, `Tagged`, `ForceBase64`, `ForceBytes` and the `blob::serde`
+//! helper modules are unavailable.
+//!
+//! With the `zeroize` feature, `Blob` implements `Zeroize` and `ZeroizeOnDrop`, wiping its bytes
+//! when dropped, though not those moved out with `into_vec`. Key material belongs in a
+//! `SecretBlob`, which also wipes the buffers it encodes and decodes through, though `Base58` and
+//! `Crockford` still use working buffers that are not.
 //!
 //! Example using `FromStr::from_str`:
 //!
@@ -23,166 +50,2710 @@
 //! }
 //! ```
 
-#![deny(missing_docs)]
+#![no_std]
+#![deny(missing_docs)]
+
+// `format!` and `vec!` are only used by some features
+#[allow(unused_imports)]
+#[macro_use]
+extern crate alloc as alloc_crate;
+#[cfg(feature = "actix")]
+extern crate actix_web;
+#[cfg(feature = "allocator-api2")]
+extern crate allocator_api2;
+#[cfg(feature = "axum")]
+extern crate axum;
+extern crate base64;
+#[cfg(feature = "simd")]
+extern crate base64_simd;
+#[cfg(feature = "bincode")]
+extern crate bincode;
+#[cfg(feature = "blake3")]
+extern crate blake3;
+#[cfg(feature = "borsh")]
+extern crate borsh;
+#[cfg(feature = "bumpalo")]
+extern crate bumpalo;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "aead")]
+extern crate chacha20poly1305;
+#[cfg(feature = "crc")]
+extern crate crc;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "digest")]
+extern crate digest;
+#[cfg(any(feature = "actix", feature = "futures"))]
+extern crate futures_core;
+#[cfg(feature = "futures-io")]
+extern crate futures_io;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "infer")]
+extern crate infer;
+#[cfg(feature = "wasm")]
+extern crate js_sys;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "napi")]
+extern crate napi;
+#[cfg(feature = "pyo3")]
+extern crate pyo3;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "serde")]
+extern crate serde as serde_crate;
+#[cfg(feature = "serde_with")]
+extern crate serde_with;
+#[cfg(feature = "sha2")]
+extern crate sha2;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "subtle")]
+extern crate subtle;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "utoipa")]
+extern crate utoipa;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen_futures;
+#[cfg(feature = "wasm")]
+extern crate web_sys;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
+
+#[cfg(feature = "tokio")]
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::string::String;
+use alloc_crate::vec::{IntoIter, Vec};
+use core::any;
+use core::borrow::{Borrow, BorrowMut};
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+#[cfg(any(feature = "futures-io", feature = "tokio", feature = "wasm"))]
+use core::future::Future;
+use core::hash::{Hash, Hasher};
+use core::hint;
+use core::iter::{Extend, FromIterator, IntoIterator};
+use core::marker::PhantomData;
+use core::mem::{self, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+use core::slice::{Iter, IterMut};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use base64::engine::GeneralPurpose;
+use base64::Engine;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsValue;
+
+#[cfg(feature = "actix")]
+mod actix;
+mod aligned;
+#[cfg(feature = "allocator-api2")]
+mod alloc;
+mod armor;
+#[cfg(any(feature = "futures", feature = "futures-io", feature = "tokio"))]
+mod async_io;
+mod base32;
+mod base58;
+mod base85;
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "serde")]
+mod cbor;
+mod chunked;
+mod chunks;
+#[cfg(feature = "cid")]
+mod cid;
+#[cfg(any(feature = "bincode", feature = "borsh", feature = "rkyv"))]
+mod codec;
+#[cfg(feature = "subtle")]
+mod constant_time;
+mod cow;
+mod custom;
+mod data_url;
+mod dynamic;
+mod error;
+#[cfg(feature = "serde")]
+mod extjson;
+#[cfg(feature = "axum")]
+mod extract;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod file;
+mod fixed;
+#[cfg(feature = "serde")]
+mod force;
+#[cfg(feature = "std")]
+mod hashed;
+#[cfg(feature = "heapless")]
+mod heapless_blob;
+mod hex;
+mod in_place;
+#[cfg(feature = "defmt")]
+mod logging;
+#[cfg(feature = "mmap")]
+mod mapped;
+mod multibase;
+#[cfg(feature = "napi")]
+mod node;
+#[cfg(feature = "utoipa")]
+mod openapi;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pem;
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "aead")]
+mod sealed;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod shared;
+#[cfg(feature = "hmac")]
+mod signed;
+#[cfg(feature = "simd")]
+mod simd;
+mod small;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod whitespace;
+mod wrapper;
+
+#[cfg(feature = "actix")]
+pub use actix::{BlobExtractFut, BlobPayloadConfig};
+pub use aligned::{Align16, Align4096, Align64, AlignedBlob, Alignment};
+#[cfg(feature = "allocator-api2")]
+pub use alloc::AllocBlob;
+#[cfg(all(feature = "allocator-api2", feature = "serde"))]
+pub use alloc::AllocSeed;
+#[cfg(feature = "bumpalo")]
+pub use alloc::BumpBlob;
+pub use armor::ArmorError;
+#[cfg(feature = "futures")]
+pub use async_io::EncodeStream;
+#[cfg(feature = "bytes")]
+pub use buf::BytesBlob;
+#[cfg(feature = "serde")]
+pub use cbor::Tagged;
+pub use chunked::{ChunkedBlob, DEFAULT_SEGMENT_LEN};
+pub use chunks::EncodeChunks;
+#[cfg(feature = "cid")]
+pub use cid::{Multicodec, Multihash};
+pub use cow::CowBlob;
+pub use custom::{AlphabetError, DynConfig};
+pub use data_url::DataUrlError;
+pub use dynamic::{DynBlob, Encoding};
+pub use error::{Error, ErrorKind};
+#[cfg(feature = "axum")]
+pub use extract::{Base64Body, Base64BodyRejection};
+pub use fixed::FixedBlob;
+#[cfg(feature = "serde")]
+pub use force::{ForceBase64, ForceBytes};
+#[cfg(feature = "std")]
+pub use hashed::HashedBlob;
+#[cfg(feature = "heapless")]
+pub use heapless_blob::HeaplessBlob;
+#[cfg(feature = "mmap")]
+pub use mapped::MmapBlob;
+pub use multibase::Multibase;
+pub use pem::PemError;
+#[cfg(feature = "aead")]
+pub use sealed::{OpenError, SealedBlob};
+#[cfg(feature = "zeroize")]
+pub use secret::SecretBlob;
+#[cfg(feature = "serde")]
+pub use serde_impl::LenientBlob;
+pub use shared::{CowShared, SharedBlob};
+#[cfg(all(feature = "hmac", feature = "serde"))]
+pub use signed::VerifyingSeed;
+#[cfg(feature = "hmac")]
+pub use signed::{SignatureError, SignedBlob};
+pub use small::SmallBlob;
+#[cfg(feature = "std")]
+pub use stream::{BlobReader, DecodingWriter, EncodeReader};
+pub use wrapper::{Base64, BlobRef};
+
+#[cfg(feature = "serde")]
+use serde_impl::{check_raw_len, serialize_bytes, BlobVisitor, SeqByte};
+
+/// Returns an upper bound on the number of bytes that `encoded_len` symbols of base-64 decode to
+///
+/// The bound exceeds the decoded length by at most two bytes, and can size decoding buffers
+/// without inspecting the input. It also bounds base-32 input. `Blob::encoded_len` gives the
+/// exact encoded length of a blob for every config.
+#[inline]
+pub fn decoded_len_estimate(encoded_len: usize) -> usize {
+    encoded_len.div_ceil(4) * 3
+}
+
+mod seal {
+    /// Restricts `Config` to the configs of this crate, so its decoding methods can change
+    pub trait Sealed {}
+}
+
+/// Trait used for statically typed Blob encoding configs
+///
+/// This trait is sealed. Custom base-64 configs implement
+/// [`Base64Config`](trait.Base64Config.html), or use `define_config!`, which provides this
+/// trait automatically. Its decoding methods are internal, so decode with the methods of
+/// `Blob` instead, which return an [`Error`](struct.Error.html).
+pub trait Config: Send + Sync + seal::Sealed {
+    /// Maximum number of decoded bytes accepted when decoding into a `Blob`, if limited
+    ///
+    /// See [`Bounded`](struct.Bounded.html) to limit an existing config.
+    const MAX_DECODED_LEN: Option<usize> = None;
+
+    /// Whether decoding accepts non-zero trailing bits in the last symbol
+    ///
+    /// This is honored by base-64 configs, while other built-in configs always reject them, as
+    /// `Ascii85` does a final partial group that is not exactly as it would be encoded.
+    const ALLOW_TRAILING_BITS: bool = false;
+
+    /// Whether `Blob` decoding only accepts the exact form `encode` would produce
+    ///
+    /// Input must then use the padding, letter case and line breaks of `encode`, except that
+    /// lenient decoding still skips whitespace.
+    const REQUIRE_CANONICAL: bool = false;
+
+    /// Whether human-readable formats serialize a `Blob` as an array of numbers
+    ///
+    /// See [`AsArray`](struct.AsArray.html) to serialize an existing config as an array.
+    const SERIALIZE_AS_SEQ: bool = false;
+
+    /// The JSON Schema `contentEncoding` of the encoded form, such as `"base64"`, if any
+    const CONTENT_ENCODING: Option<&'static str> = None;
+
+    /// Number of bytes encoded independently of any others, if the encoding works in blocks
+    ///
+    /// Encoding consecutive multiples of this many bytes and then the rest, one after another,
+    /// must produce the same output as encoding all of it at once. Data split into pieces,
+    /// as with `ChunkedBlob`, is otherwise made contiguous before encoding.
+    const BLOCK_LEN: Option<usize> = None;
+
+    /// Number of symbols that decode independently of any others, if the encoding works in blocks
+    ///
+    /// Decoding consecutive multiples of this many symbols, ignoring whitespace, and then the
+    /// rest, where only the rest may contain `=` padding, must produce the same output as
+    /// decoding all of it at once. Otherwise, `Blob::decode_from_reader` reads all its input
+    /// before decoding.
+    const ENCODED_BLOCK_LEN: Option<usize> = None;
+
+    /// Appends the encoded form of `input` to `output`
+    fn encode(input: &[u8], output: &mut String);
+
+    /// Decodes `input`, appending the decoded bytes to `output`
+    #[doc(hidden)]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError>;
+
+    /// Decodes `input` like `decode`, but skipping any ASCII whitespace
+    ///
+    /// The default implementation decodes from a cleaned copy of `input`.
+    #[doc(hidden)]
+    fn decode_lenient(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        whitespace::decode_cleaned(input, output, Self::decode)
+    }
+
+    /// Decodes `input` like `decode`, but accepting input both with and without padding
+    ///
+    /// The default implementation forwards to `decode`, which is correct for encodings
+    /// without padding, or ones that are already indifferent to it.
+    #[doc(hidden)]
+    #[inline]
+    fn decode_any_padding(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Self::decode(input, output)
+    }
+
+    /// Decodes `input` like `decode`, but only accepting the padding `encode` produces
+    ///
+    /// The default implementation forwards to `decode`, which is correct for encodings
+    /// without padding, or ones that are already strict about it. `Blob::decode_base64_strict`
+    /// checks the rest of the form afterwards.
+    #[doc(hidden)]
+    #[inline]
+    fn decode_strict(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Self::decode(input, output)
+    }
+
+    /// Checks that `input` would decode, returning the decoded length
+    ///
+    /// The default implementation decodes into a temporary buffer, so configs override it to
+    /// validate without allocating where they can.
+    #[doc(hidden)]
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        let mut output = Vec::new();
+
+        Self::decode(input, &mut output).map(|_| output.len())
+    }
+
+    /// Decodes `input` like `decode`, but into the start of `output`, returning the decoded
+    /// length
+    ///
+    /// Returns `None` if the config cannot decode into a slice, or if `output` is too short,
+    /// in which case callers decode with `decode` instead. The default implementation always
+    /// returns `None`.
+    #[doc(hidden)]
+    #[inline]
+    fn decode_to_slice(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, base64::DecodeError>> {
+        let _ = (input, output);
+
+        None
+    }
+
+    /// Returns the least and greatest number of bytes that `decode` could produce from
+    /// `encoded_len` bytes of valid input
+    ///
+    /// The default implementation gives no bounds at all.
+    #[doc(hidden)]
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        let _ = encoded_len;
+
+        (0, usize::MAX)
+    }
+
+    /// Writes the encoded form of `input` to a formatter
+    ///
+    /// The default implementation encodes to an intermediate `String`.
+    fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        let mut encoded = String::new();
+
+        Self::encode(input, &mut encoded);
+
+        f.write_str(&encoded)
+    }
+
+    /// Writes the encoded form of `input` to an `io::Write`
+    ///
+    /// The default implementation encodes to an intermediate `String`.
+    #[cfg(feature = "std")]
+    fn encode_to<W: io::Write>(input: &[u8], mut writer: W) -> io::Result<()> {
+        let mut encoded = String::new();
+
+        Self::encode(input, &mut encoded);
+
+        writer.write_all(encoded.as_bytes())
+    }
+
+    /// Returns the length in bytes of the encoded form of `input`
+    ///
+    /// The default implementation encodes to an intermediate `String`.
+    fn encoded_len(input: &[u8]) -> usize {
+        let mut encoded = String::new();
+
+        Self::encode(input, &mut encoded);
+
+        encoded.len()
+    }
+
+    /// Writes the encoded form of `input` to the start of `output`, returning its length,
+    /// or `None` if `output` is too short
+    ///
+    /// The default implementation encodes to an intermediate `String`.
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+        let mut encoded = String::new();
+
+        Self::encode(input, &mut encoded);
+
+        let len = encoded.len();
+
+        output.get_mut(..len)?.copy_from_slice(encoded.as_bytes());
+
+        Some(len)
+    }
+}
+
+/// Trait used for statically typed base-64 Blob encoding configs
+///
+/// The built-in configs decode input both with and without padding, regardless of whether
+/// they pad when encoding, using `DecodePaddingMode::Indifferent`.
+pub trait Base64Config: Send + Sync {
+    /// Associated base-64 engine
+    const ENGINE: GeneralPurpose;
+
+    /// Whether decoding accepts non-zero trailing bits, as with `Config::ALLOW_TRAILING_BITS`
+    const ALLOW_TRAILING_BITS: bool = false;
+
+    /// Whether decoding requires the canonical encoding, as with `Config::REQUIRE_CANONICAL`
+    const REQUIRE_CANONICAL: bool = false;
+
+    /// The JSON Schema `contentEncoding` of the encoded form, as with `Config::CONTENT_ENCODING`
+    const CONTENT_ENCODING: Option<&'static str> = None;
+
+    /// The alphabet of `ENGINE`, if the `simd` feature can encode and decode it identically
+    #[doc(hidden)]
+    const SIMD_ALPHABET: Option<__private::SimdAlphabet> = None;
+}
+
+impl<T: Base64Config> seal::Sealed for T {}
+
+impl<T: Base64Config> Config for T {
+    const ALLOW_TRAILING_BITS: bool = <T as Base64Config>::ALLOW_TRAILING_BITS;
+    const REQUIRE_CANONICAL: bool = <T as Base64Config>::REQUIRE_CANONICAL;
+    const CONTENT_ENCODING: Option<&'static str> = <T as Base64Config>::CONTENT_ENCODING;
+    const BLOCK_LEN: Option<usize> = Some(3);
+    const ENCODED_BLOCK_LEN: Option<usize> = Some(4);
+
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        #[cfg(feature = "simd")]
+        {
+            if simd::encode::<T>(input, output) {
+                return;
+            }
+        }
+
+        T::ENGINE.encode_string(input, output)
+    }
+
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        #[cfg(feature = "simd")]
+        {
+            if !<T as Base64Config>::ALLOW_TRAILING_BITS && simd::decode::<T>(input, output) {
+                return Ok(());
+            }
+        }
+
+        let len = output.len();
+
+        match T::ENGINE.decode_vec(input, output) {
+            Err(base64::DecodeError::InvalidLastSymbol(i, _))
+                if <T as Base64Config>::ALLOW_TRAILING_BITS =>
+            {
+                output.truncate(len);
+
+                let cleared = custom::clear_trailing_bits(&T::ENGINE, input, i);
+
+                T::ENGINE.decode_vec(cleared, output)
+            }
+            result => result,
+        }
+    }
+
+    #[inline]
+    fn decode_lenient(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        whitespace::decode(input, output, T::decode)
+    }
+
+    fn decode_strict(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        let canonical = if base64::engine::Config::encode_padding(T::ENGINE.config()) {
+            input.len().is_multiple_of(4)
+        } else {
+            !input.ends_with(b"=")
+        };
+
+        if !canonical {
+            return Err(base64::DecodeError::InvalidPadding);
+        }
+
+        T::decode(input, output)
+    }
+
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        // each chunk of 1024 symbols decodes to at most 768 bytes
+        let mut buffer = [0u8; 768];
+        let mut len = 0;
+
+        for (i, chunk) in input.chunks(1024).enumerate() {
+            let offset = i * 1024;
+
+            // padding is only valid in the final chunk
+            if offset + chunk.len() < input.len() {
+                if let Some(j) = chunk.iter().position(|&c| c == b'=') {
+                    return Err(base64::DecodeError::InvalidByte(offset + j, b'='));
+                }
+            }
+
+            let decoded = match T::ENGINE.decode_slice_unchecked(chunk, &mut buffer) {
+                Err(base64::DecodeError::InvalidLastSymbol(j, _))
+                    if <T as Base64Config>::ALLOW_TRAILING_BITS =>
+                {
+                    let mut cleared = [0u8; 1024];
+                    let cleared = &mut cleared[..chunk.len()];
+
+                    cleared.copy_from_slice(chunk);
+                    custom::clear_trailing_bits_in_place(&T::ENGINE, cleared, j);
+
+                    T::ENGINE.decode_slice_unchecked(&*cleared, &mut buffer)
+                }
+                result => result,
+            };
+
+            len += decoded.map_err(|err| whitespace::shift_offsets(err, offset))?;
+        }
+
+        Ok(len)
+    }
+
+    fn decode_to_slice(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, base64::DecodeError>> {
+        match T::ENGINE.decode_slice(input, output) {
+            Ok(len) => Some(Ok(len)),
+            // clearing the trailing bits needs a copy of the input, so `decode` handles them
+            Err(base64::DecodeSliceError::DecodeError(base64::DecodeError::InvalidLastSymbol(
+                ..,
+            ))) if <T as Base64Config>::ALLOW_TRAILING_BITS => None,
+            Err(base64::DecodeSliceError::DecodeError(err)) => Some(Err(err)),
+            Err(base64::DecodeSliceError::OutputSliceTooSmall) => None,
+        }
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        // up to two symbols may be padding
+        (
+            encoded_len.saturating_sub(2) * 3 / 4,
+            decoded_len_estimate(encoded_len),
+        )
+    }
+
+    #[inline]
+    fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        base64::display::Base64Display::new(input, &T::ENGINE).fmt(f)
+    }
+
+    #[cfg(feature = "std")]
+    fn encode_to<W: io::Write>(input: &[u8], mut writer: W) -> io::Result<()> {
+        let engine = T::ENGINE;
+        let mut encoder = base64::write::EncoderWriter::new(&mut writer, &engine);
+
+        encoder.write_all(input)
+    }
+
+    #[inline]
+    fn encoded_len(input: &[u8]) -> usize {
+        use base64::engine::Config as _;
+
+        let padding = T::ENGINE.config().encode_padding();
+
+        base64::encoded_len(input.len(), padding).expect("encoded length overflows usize")
+    }
+
+    #[inline]
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+        T::ENGINE.encode_slice(input, output).ok()
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use base64::engine::GeneralPurpose;
+    pub use custom::{alphabet_or_panic, engine};
+
+    /// Alphabets supported by the `simd` feature
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SimdAlphabet {
+        Standard,
+        UrlSafe,
+    }
+}
+
+macro_rules! simd_alphabet {
+    (STANDARD) => {
+        Some(__private::SimdAlphabet::Standard)
+    };
+    (URL_SAFE) => {
+        Some(__private::SimdAlphabet::UrlSafe)
+    };
+    ($alphabet:ident) => {
+        None
+    };
+}
+
+/// Defines a base-64 `Config` with a custom alphabet, validated at compile time
+///
+/// The alphabet must consist of 64 unique printable ASCII characters, excluding `=`,
+/// and the second argument specifies whether encoding adds padding. Decoding accepts
+/// either padded or unpadded input, as with the built-in configs.
+///
+/// ```
+/// #[macro_use]
+/// extern crate blob;
+///
+/// use blob::Blob;
+///
+/// define_config! {
+///     /// Standard alphabet with the digits first
+///     pub DigitsFirst = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz+/", true
+/// }
+///
+/// fn main() {
+///     let blob: Blob<DigitsFirst> = Blob::from(&[1, 2, 3, 4, 5][..]);
+///
+///     assert_eq!(blob.encode_base64(), "0G8310K=");
+/// }
+/// ```
+///
+/// Invalid alphabets fail to compile:
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate blob;
+///
+/// define_config!(pub TooShort = "ABC", true);
+///
+/// fn main() {}
+/// ```
+#[macro_export]
+macro_rules! define_config {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $alphabet:expr, $pad:expr) => {
+        $(#[$attr])*
+        $vis enum $name {}
+
+        impl $crate::Base64Config for $name {
+            const ENGINE: $crate::__private::GeneralPurpose = $crate::__private::engine(
+                &$crate::__private::alphabet_or_panic($alphabet.as_bytes()),
+                $pad,
+            );
+        }
+
+        // force evaluation of the engine, so invalid alphabets are always a compile error
+        const _: $crate::__private::GeneralPurpose = <$name as $crate::Base64Config>::ENGINE;
+    };
+}
+
+macro_rules! impl_configs {
+    ($($(#[$($attrs:tt)*])* $name:ident: $alphabet:ident, $pad:expr $(, $encoding:expr)?;)*) => {
+        $(
+            $(#[$($attrs)*])*
+            pub enum $name {}
+
+            impl Base64Config for $name {
+                const ENGINE: GeneralPurpose = custom::engine(&base64::alphabet::$alphabet, $pad);
+                const SIMD_ALPHABET: Option<__private::SimdAlphabet> = simd_alphabet!($alphabet);
+                $(const CONTENT_ENCODING: Option<&'static str> = Some($encoding);)?
+            }
+        )*
+    }
+}
+
+impl_configs! {
+    /// As per `crypt(3)` requirements
+    Crypt: CRYPT, false;
+
+    /// Standard character set with padding.
+    Standard: STANDARD, true, "base64";
+
+    /// Standard character set without padding.
+    StandardNoPad: STANDARD, false, "base64";
+
+    /// URL-safe character set with padding
+    UrlSafe: URL_SAFE, true, "base64url";
+
+    /// URL-safe character set without padding
+    UrlSafeNoPad: URL_SAFE, false, "base64url";
+
+    /// IMAP modified base-64, as used by modified UTF-7 mailbox names (RFC 3501)
+    ///
+    /// The standard character set with `,` instead of `/`, without padding.
+    ImapMutf7: IMAP_MUTF7, false;
+
+    /// Base-64 variant used by bcrypt hashes and salts, without padding
+    Bcrypt: BCRYPT, false;
+}
+
+/// RFC 2045 MIME base-64, with the standard character set and padding
+///
+/// Encoding folds the output into lines of at most 76 characters, separated by CRLF,
+/// without a trailing line break. Decoding ignores any ASCII whitespace, including line breaks.
+pub enum Mime {}
+
+impl seal::Sealed for Mime {}
+
+impl Config for Mime {
+    const CONTENT_ENCODING: Option<&'static str> = Some("base64");
+    const ENCODED_BLOCK_LEN: Option<usize> = Some(4);
+
+    fn encode(input: &[u8], output: &mut String) {
+        // 57 bytes encode to exactly 76 characters
+        for (i, line) in input.chunks(57).enumerate() {
+            if i > 0 {
+                output.push_str("\r\n");
+            }
+
+            Standard::encode(line, output);
+        }
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        whitespace::decode(input, output, Standard::decode)
+    }
+
+    #[inline]
+    fn decode_lenient(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Mime::decode(input, output)
+    }
+
+    #[inline]
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        whitespace::validate(input, Standard::validate)
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        // the input may be entirely whitespace
+        (0, decoded_len_estimate(encoded_len))
+    }
+}
+
+macro_rules! impl_hex_configs {
+    ($($(#[$($attrs:tt)*])* $name:ident: $table:ident,)*) => {
+        $(
+            $(#[$($attrs)*])*
+            pub enum $name {}
+
+            impl seal::Sealed for $name {}
+
+            impl Config for $name {
+                const CONTENT_ENCODING: Option<&'static str> = Some("base16");
+                const BLOCK_LEN: Option<usize> = Some(1);
+                const ENCODED_BLOCK_LEN: Option<usize> = Some(2);
+
+                #[inline]
+                fn encode(input: &[u8], output: &mut String) {
+                    hex::encode(input, hex::$table, output)
+                }
+
+                #[inline]
+                fn encoded_len(input: &[u8]) -> usize {
+                    input.len() * 2
+                }
+
+                #[inline]
+                fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+                    hex::encode_to_slice(input, hex::$table, output)
+                }
+
+                #[inline]
+                fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+                    hex::decode(input, output)
+                }
+
+                #[inline]
+                fn decode_lenient(
+                    input: &[u8],
+                    output: &mut Vec<u8>,
+                ) -> Result<(), base64::DecodeError> {
+                    whitespace::decode(input, output, hex::decode)
+                }
+
+                #[inline]
+                fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+                    hex::validate(input)
+                }
+
+                #[inline]
+                fn decode_to_slice(
+                    input: &[u8],
+                    output: &mut [u8],
+                ) -> Option<Result<usize, base64::DecodeError>> {
+                    hex::decode_to_slice(input, output)
+                }
+
+                #[inline]
+                fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+                    (encoded_len / 2, encoded_len / 2)
+                }
+
+                #[inline]
+                fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+                    hex::fmt(input, hex::$table, f)
+                }
+            }
+        )*
+    }
+}
+
+impl_hex_configs! {
+    /// Lowercase hexadecimal, decoding either case
+    Hex: LOWER,
+
+    /// Uppercase hexadecimal, decoding either case
+    HexUpper: UPPER,
+}
+
+macro_rules! impl_base32_configs {
+    ($($(#[$($attrs:tt)*])* $name:ident: $alphabet:ident, $pad:expr, $encoding:expr;)*) => {
+        $(
+            $(#[$($attrs)*])*
+            pub enum $name {}
+
+            impl seal::Sealed for $name {}
+
+            impl Config for $name {
+                const CONTENT_ENCODING: Option<&'static str> = Some($encoding);
+                const BLOCK_LEN: Option<usize> = Some(5);
+                const ENCODED_BLOCK_LEN: Option<usize> = Some(8);
+
+                #[inline]
+                fn encode(input: &[u8], output: &mut String) {
+                    base32::encode(input, &base32::$alphabet, $pad, output)
+                }
+
+                #[inline]
+                fn encoded_len(input: &[u8]) -> usize {
+                    base32::encoded_len(input.len(), $pad)
+                }
+
+                #[inline]
+                fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+                    let padding = base32::Padding::from_pad($pad);
+
+                    base32::decode(input, &base32::$alphabet, padding, output)
+                }
+
+                #[inline]
+                fn decode_any_padding(
+                    input: &[u8],
+                    output: &mut Vec<u8>,
+                ) -> Result<(), base64::DecodeError> {
+                    base32::decode(input, &base32::$alphabet, base32::Padding::Indifferent, output)
+                }
+
+                #[inline]
+                fn decode_lenient(
+                    input: &[u8],
+                    output: &mut Vec<u8>,
+                ) -> Result<(), base64::DecodeError> {
+                    whitespace::decode(input, output, $name::decode)
+                }
+
+                #[inline]
+                fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+                    let padding = base32::Padding::from_pad($pad);
+
+                    base32::validate(input, &base32::$alphabet, padding)
+                }
+
+                #[inline]
+                fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+                    // up to six symbols may be padding
+                    (encoded_len.saturating_sub(6) * 5 / 8, encoded_len * 5 / 8)
+                }
+            }
+        )*
+    }
+}
+
+impl_base32_configs! {
+    /// RFC 4648 base-32 with padding, decoding either case
+    Base32: STANDARD, true, "base32";
+
+    /// RFC 4648 base-32 without padding, decoding either case
+    Base32NoPad: STANDARD, false, "base32";
+
+    /// RFC 4648 base-32 with the "Extended Hex" alphabet and padding, decoding either case
+    Base32Hex: HEX, true, "base32hex";
+
+    /// RFC 4648 base-32 with the "Extended Hex" alphabet and without padding, decoding either case
+    Base32HexNoPad: HEX, false, "base32hex";
+}
+
+macro_rules! impl_crockford_configs {
+    ($($(#[$($attrs:tt)*])* $name:ident: $check:expr, $block_len:expr;)*) => {
+        $(
+            $(#[$($attrs)*])*
+            pub enum $name {}
+
+            impl seal::Sealed for $name {}
+
+            impl Config for $name {
+                const BLOCK_LEN: Option<usize> = $block_len;
+
+                #[inline]
+                fn encode(input: &[u8], output: &mut String) {
+                    base32::encode_crockford(input, $check, output)
+                }
+
+                #[inline]
+                fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+                    base32::decode_crockford(input, $check, output)
+                }
+
+                #[inline]
+                fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+                    base32::validate_crockford(input, $check)
+                }
+
+                #[inline]
+                fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+                    // the input may be entirely hyphens
+                    (0, encoded_len * 5 / 8)
+                }
+            }
+        )*
+    }
+}
+
+impl_crockford_configs! {
+    /// Crockford's base-32, without padding
+    ///
+    /// Decoding is case-insensitive, ignores hyphens, and reads `O` as `0` and `I`/`L` as `1`.
+    Crockford: false, Some(5);
+
+    /// Crockford's base-32 with a trailing mod-37 check symbol
+    ///
+    /// The check symbol is computed over the value of the encoded symbols. Decoding is as with
+    /// [`Crockford`](enum.Crockford.html), but the check symbol is required and verified.
+    CrockfordCheck: true, None;
+}
+
+/// Base-58 with the Bitcoin alphabet
+pub enum Base58 {}
+
+impl seal::Sealed for Base58 {}
+
+impl Config for Base58 {
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        base58::encode(input, output)
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        base58::decode(input, output)
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        // each symbol after the first carries at least log256(58) > 0.732 bytes, and a leading
+        // `1` carries a whole zero byte
+        (encoded_len.saturating_sub(1) * 732 / 1000, encoded_len)
+    }
+}
+
+/// Adobe-style Ascii85, without the `<~ ~>` delimiters
+///
+/// Decoding ignores ASCII whitespace and optional `<~ ~>` delimiters.
+pub enum Ascii85 {}
+
+impl seal::Sealed for Ascii85 {}
+
+impl Config for Ascii85 {
+    const BLOCK_LEN: Option<usize> = Some(4);
+
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        base85::encode_ascii85(input, output)
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        base85::decode_ascii85(input, output)
+    }
+
+    #[inline]
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        base85::validate_ascii85(input)
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        // the input may be entirely whitespace, and each `z` decodes to four bytes
+        (0, encoded_len.saturating_mul(4))
+    }
+}
+
+/// Wrapper config limiting the decoded length of another config `C` to `MAX` bytes
+///
+/// Encoding is identical to `C`. When decoding with `decode_base64`, `decode_base64_strict`,
+/// `append_base64`, `FromStr` or `Deserialize`, oversized input is rejected before allocating
+/// for the decoded bytes, from its length alone where that suffices, and otherwise by
+/// validating it without allocating. Lenient and padding-agnostic decoding check the limit
+/// after decoding instead, and byte sequences are rejected as soon as they exceed it.
+///
+/// ```
+/// extern crate blob;
+///
+/// use blob::{Blob, Bounded, ErrorKind, Standard};
+///
+/// fn main() {
+///     type Small = Blob<Bounded<Standard, 4>>;
+///
+///     assert_eq!(Small::decode_base64("AQIDBA==").unwrap(), [1, 2, 3, 4]);
+///     assert_eq!(Small::decode_base64("AQIDBAU=").unwrap_err().kind(), ErrorKind::TooLong);
+/// }
+/// ```
+pub struct Bounded<C: Config, const MAX: usize> {
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config, const MAX: usize> seal::Sealed for Bounded<C, MAX> {}
+
+impl<C: Config, const MAX: usize> Config for Bounded<C, MAX> {
+    const MAX_DECODED_LEN: Option<usize> = Some(MAX);
+    const ALLOW_TRAILING_BITS: bool = C::ALLOW_TRAILING_BITS;
+    const REQUIRE_CANONICAL: bool = C::REQUIRE_CANONICAL;
+    const SERIALIZE_AS_SEQ: bool = C::SERIALIZE_AS_SEQ;
+    const CONTENT_ENCODING: Option<&'static str> = C::CONTENT_ENCODING;
+    const BLOCK_LEN: Option<usize> = C::BLOCK_LEN;
+    const ENCODED_BLOCK_LEN: Option<usize> = C::ENCODED_BLOCK_LEN;
+
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        C::encode(input, output)
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode(input, output)
+    }
+
+    #[inline]
+    fn decode_lenient(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_lenient(input, output)
+    }
+
+    #[inline]
+    fn decode_any_padding(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_any_padding(input, output)
+    }
+
+    #[inline]
+    fn decode_strict(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_strict(input, output)
+    }
+
+    #[inline]
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        C::validate(input)
+    }
+
+    #[inline]
+    fn decode_to_slice(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, base64::DecodeError>> {
+        C::decode_to_slice(input, output)
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        C::decoded_len_bounds(encoded_len)
+    }
+
+    #[inline]
+    fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(input, f)
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    fn encode_to<W: io::Write>(input: &[u8], writer: W) -> io::Result<()> {
+        C::encode_to(input, writer)
+    }
+
+    #[inline]
+    fn encoded_len(input: &[u8]) -> usize {
+        C::encoded_len(input)
+    }
+
+    #[inline]
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+        C::encode_to_slice(input, output)
+    }
+}
+
+/// Wrapper config serializing a `Blob` of another config `C` as an array of numbers
+///
+/// Human-readable formats then serialize `[1,2,3]` instead of an encoded string, while binary
+/// formats still store the raw bytes. Deserialization is identical to `C`, which already
+/// accepts both forms, and so is encoding with `encode_base64` or `Display`.
+///
+/// ```
+/// extern crate blob;
+/// extern crate serde_json;
+///
+/// use blob::{AsArray, Blob, Standard};
+///
+/// # #[cfg(feature = "serde")]
+/// fn main() {
+///     let blob: Blob<AsArray<Standard>> = Blob::from(&[1, 2, 3][..]);
+///
+///     assert_eq!(serde_json::to_string(&blob).unwrap(), "[1,2,3]");
+///     assert_eq!(serde_json::from_str::<Blob<AsArray>>(r#""AQID""#).unwrap(), blob);
+/// }
+/// # #[cfg(not(feature = "serde"))]
+/// # fn main() {}
+/// ```
+pub struct AsArray<C: Config = Standard> {
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> seal::Sealed for AsArray<C> {}
+
+impl<C: Config> Config for AsArray<C> {
+    const MAX_DECODED_LEN: Option<usize> = C::MAX_DECODED_LEN;
+    const ALLOW_TRAILING_BITS: bool = C::ALLOW_TRAILING_BITS;
+    const REQUIRE_CANONICAL: bool = C::REQUIRE_CANONICAL;
+    const SERIALIZE_AS_SEQ: bool = true;
+    const CONTENT_ENCODING: Option<&'static str> = C::CONTENT_ENCODING;
+    const BLOCK_LEN: Option<usize> = C::BLOCK_LEN;
+    const ENCODED_BLOCK_LEN: Option<usize> = C::ENCODED_BLOCK_LEN;
+
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        C::encode(input, output)
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode(input, output)
+    }
+
+    #[inline]
+    fn decode_lenient(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_lenient(input, output)
+    }
+
+    #[inline]
+    fn decode_any_padding(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_any_padding(input, output)
+    }
+
+    #[inline]
+    fn decode_strict(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        C::decode_strict(input, output)
+    }
+
+    #[inline]
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        C::validate(input)
+    }
+
+    #[inline]
+    fn decode_to_slice(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, base64::DecodeError>> {
+        C::decode_to_slice(input, output)
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        C::decoded_len_bounds(encoded_len)
+    }
+
+    #[inline]
+    fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(input, f)
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    fn encode_to<W: io::Write>(input: &[u8], writer: W) -> io::Result<()> {
+        C::encode_to(input, writer)
+    }
+
+    #[inline]
+    fn encoded_len(input: &[u8]) -> usize {
+        C::encoded_len(input)
+    }
+
+    #[inline]
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+        C::encode_to_slice(input, output)
+    }
+}
+
+/// Wrapper config decoding either hexadecimal or the encoding of another config `C`
+///
+/// Input with a `0x` or `0X` prefix is decoded as hex, and anything else with `C`, so that
+/// base-64 strings which happen to consist of hex digits, such as `"AAAA"`, still decode as
+/// base-64. Encoding is identical to `C`, so every blob survives a round trip, and the input
+/// is never required to be canonical.
+///
+/// ```
+/// extern crate blob;
+///
+/// use blob::{Blob, HexOrBase64};
+///
+/// fn main() {
+///     type Feed = Blob<HexOrBase64>;
+///
+///     assert_eq!(Feed::decode_base64("0x01ff").unwrap(), [1, 255]);
+///     assert_eq!(Feed::decode_base64("AAAA").unwrap(), [0, 0, 0]);
+///     assert_eq!(Feed::decode_base64("Af8=").unwrap(), [1, 255]);
+///     assert_eq!(Feed::from(&[1, 255][..]).encode_base64(), "Af8=");
+/// }
+/// ```
+pub struct HexOrBase64<C: Config = Standard> {
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> HexOrBase64<C> {
+    /// Returns the hex digits of `input` and their offset, if it has a `0x` or `0X` prefix
+    fn hex_digits(input: &[u8]) -> Option<(usize, &[u8])> {
+        if input.starts_with(b"0x") || input.starts_with(b"0X") {
+            Some((2, &input[2..]))
+        } else {
+            None
+        }
+    }
+
+    /// Decodes `input` as hex if it should be, otherwise with `decode`
+    fn decode_either<F>(
+        input: &[u8],
+        output: &mut Vec<u8>,
+        decode: F,
+    ) -> Result<(), base64::DecodeError>
+    where
+        F: FnOnce(&[u8], &mut Vec<u8>) -> Result<(), base64::DecodeError>,
+    {
+        match Self::hex_digits(input) {
+            Some((offset, digits)) => {
+                hex::decode(digits, output).map_err(|err| whitespace::shift_offsets(err, offset))
+            }
+            None => decode(input, output),
+        }
+    }
+}
+
+impl<C: Config> seal::Sealed for HexOrBase64<C> {}
+
+impl<C: Config> Config for HexOrBase64<C> {
+    const MAX_DECODED_LEN: Option<usize> = C::MAX_DECODED_LEN;
+    const ALLOW_TRAILING_BITS: bool = C::ALLOW_TRAILING_BITS;
+    const SERIALIZE_AS_SEQ: bool = C::SERIALIZE_AS_SEQ;
+    const CONTENT_ENCODING: Option<&'static str> = C::CONTENT_ENCODING;
+    const BLOCK_LEN: Option<usize> = C::BLOCK_LEN;
+
+    #[inline]
+    fn encode(input: &[u8], output: &mut String) {
+        C::encode(input, output)
+    }
+
+    #[inline]
+    fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Self::decode_either(input, output, C::decode)
+    }
+
+    #[inline]
+    fn decode_any_padding(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Self::decode_either(input, output, C::decode_any_padding)
+    }
+
+    #[inline]
+    fn decode_strict(input: &[u8], output: &mut Vec<u8>) -> Result<(), base64::DecodeError> {
+        Self::decode_either(input, output, C::decode_strict)
+    }
+
+    fn validate(input: &[u8]) -> Result<usize, base64::DecodeError> {
+        match Self::hex_digits(input) {
+            Some((offset, digits)) => {
+                hex::validate(digits).map_err(|err| whitespace::shift_offsets(err, offset))
+            }
+            None => C::validate(input),
+        }
+    }
+
+    fn decode_to_slice(
+        input: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, base64::DecodeError>> {
+        match Self::hex_digits(input) {
+            Some((offset, digits)) => hex::decode_to_slice(digits, output)
+                .map(|result| result.map_err(|err| whitespace::shift_offsets(err, offset))),
+            None => C::decode_to_slice(input, output),
+        }
+    }
+
+    #[inline]
+    fn decoded_len_bounds(encoded_len: usize) -> (usize, usize) {
+        let (min, max) = C::decoded_len_bounds(encoded_len);
+
+        // hex after a `0x` prefix
+        let hex = encoded_len.saturating_sub(2) / 2;
+
+        (min.min(hex), max.max(hex))
+    }
+
+    #[inline]
+    fn fmt(input: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(input, f)
+    }
+
+    #[inline]
+    #[cfg(feature = "std")]
+    fn encode_to<W: io::Write>(input: &[u8], writer: W) -> io::Result<()> {
+        C::encode_to(input, writer)
+    }
+
+    #[inline]
+    fn encoded_len(input: &[u8]) -> usize {
+        C::encoded_len(input)
+    }
+
+    #[inline]
+    fn encode_to_slice(input: &[u8], output: &mut [u8]) -> Option<usize> {
+        C::encode_to_slice(input, output)
+    }
+}
+
+/// Error returned when decoding checksummed data into a `Blob`
+#[cfg(feature = "sha2")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChecksumError {
+    /// The encoded data was invalid
+    Decode(Error),
+    /// The decoded data was too short to contain a checksum
+    MissingChecksum,
+    /// The checksum did not match the data
+    Mismatch,
+}
+
+#[cfg(feature = "sha2")]
+impl Display for ChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChecksumError::Decode(ref err) => err.fmt(f),
+            ChecksumError::MissingChecksum => f.write_str("Missing checksum"),
+            ChecksumError::Mismatch => f.write_str("Checksum mismatch"),
+        }
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl StdError for ChecksumError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            ChecksumError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl From<Error> for ChecksumError {
+    #[inline]
+    fn from(err: Error) -> ChecksumError {
+        ChecksumError::Decode(err)
+    }
+}
+
+/// Compares every byte of `a` and `b` when they have the same length, without an early exit
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+
+    // keep the optimizer from turning the fold back into an early exit
+    hint::black_box(diff) == 0
+}
+
+#[cfg(feature = "crc")]
+const CRC_32: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+#[cfg(feature = "crc")]
+const CRC_32C: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+#[cfg(feature = "crc")]
+const CRC_64_NVME: crc::Crc<u64> = crc::Crc::<u64>::new(&crc::CRC_64_NVME);
+
+/// First four bytes of the double SHA-256 of `data`, as used by Base58Check
+#[cfg(feature = "sha2")]
+fn double_sha256_checksum(data: &[u8]) -> [u8; 4] {
+    use sha2::{Digest, Sha256};
+
+    let hash = Sha256::digest(Sha256::digest(data));
+
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Encoding detected by [`Blob::decode_auto`](struct.Blob.html#method.decode_auto)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetectedEncoding {
+    /// Case-insensitive hexadecimal
+    Hex,
+    /// Standard base-64, with or without padding
+    Standard,
+    /// URL-safe base-64, with or without padding
+    UrlSafe,
+}
+
+/// Error returned when PKCS#7 padding cannot be removed from a `Blob`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadError {
+    /// The block size was not in the range `1..=255`
+    InvalidBlockSize(usize),
+    /// The `Blob` length is not a non-zero multiple of the block size
+    InvalidLength,
+    /// The padding bytes are malformed
+    InvalidPadding,
+}
+
+impl Display for PadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PadError::InvalidBlockSize(block) => write!(f, "Invalid block size {}", block),
+            PadError::InvalidLength => f.write_str("Length is not a multiple of the block size"),
+            PadError::InvalidPadding => f.write_str("Invalid padding"),
+        }
+    }
+}
+
+impl StdError for PadError {}
+
+/// Blob structure containing binary data
+///
+/// Interally, the blob is stored as a plain `Vec<u8>`, and some
+/// methods are exposed from that. If you need full access to the
+/// underlying `Vec`, use `borrow()` or `borrow_mut()`
+///
+/// `Blob` is `#[repr(transparent)]` over its `Vec<u8>`, so its layout never depends on `C`.
+#[repr(transparent)]
+pub struct Blob<C: Config = Standard> {
+    data: Vec<u8>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> Default for Blob<C> {
+    #[inline]
+    fn default() -> Self {
+        Blob {
+            data: Vec::new(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> Blob<C> {
+    /// Create a new empty `Blob`
+    #[inline]
+    pub fn new() -> Blob<C> {
+        Blob::default()
+    }
+
+    /// Create a `Blob` from an underlying `Vec`
+    #[inline]
+    pub fn from_vec(vec: Vec<u8>) -> Blob<C> {
+        Blob {
+            data: vec,
+            _config: PhantomData,
+        }
+    }
+
+    /// Create a new `Blob` with the given capacity
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Blob<C> {
+        Blob::from_vec(Vec::with_capacity(capacity))
+    }
+
+    /// Create a `Blob` of `len` random bytes from the thread-local generator
+    ///
+    /// The generator is cryptographically secure, so this is suitable for nonces and salts.
+    #[cfg(all(feature = "rand", feature = "std"))]
+    #[inline]
+    pub fn random(len: usize) -> Blob<C> {
+        Blob::random_with(&mut rand::thread_rng(), len)
+    }
+
+    /// Create a `Blob` of `len` random bytes from the given generator
+    #[cfg(feature = "rand")]
+    pub fn random_with<R: rand::RngCore + ?Sized>(rng: &mut R, len: usize) -> Blob<C> {
+        let mut data = vec![0; len];
+
+        rng.fill_bytes(&mut data);
+
+        Blob::from_vec(data)
+    }
+
+    /// Returns the number of bytes the `Blob` can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves capacity for at least additional more bytes to be inserted in the given `Blob`
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional)
+    }
+
+    /// Returns the number of bytes in the `Blob`
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the `Blob` contains no bytes
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns a raw pointer to the bytes of the `Blob`, suitable for passing to FFI
+    ///
+    /// The pointer is only valid for as long as the `Blob` is not mutated or moved,
+    /// as doing so may reallocate the underlying buffer.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+
+    /// Returns a mutable raw pointer to the bytes of the `Blob`, suitable for passing to FFI
+    ///
+    /// The pointer is only valid for as long as the `Blob` is not mutated or moved,
+    /// as doing so may reallocate the underlying buffer.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.data.as_mut_ptr()
+    }
+
+    /// Returns the remaining spare capacity of the `Blob` as a slice of `MaybeUninit<u8>`
+    ///
+    /// The returned slice can be filled with data before marking it as initialized
+    /// with [`set_len`](#method.set_len).
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        self.data.spare_capacity_mut()
+    }
+
+    /// Forces the length of the `Blob` to `new_len`
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity()`](#method.capacity).
+    /// - The bytes at `old_len..new_len` must be initialized, e.g. by writing
+    ///   to [`spare_capacity_mut()`](#method.spare_capacity_mut) first.
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        self.data.set_len(new_len)
+    }
+
+    /// Use a different encoding configuration for the `Blob`
+    #[inline(always)]
+    pub fn with_config<E: Config>(mut self) -> Blob<E> {
+        Blob {
+            data: mem::take(&mut self.data),
+            _config: PhantomData,
+        }
+    }
+
+    /// Borrow the `Blob` under a different encoding configuration, without consuming or cloning it
+    #[inline(always)]
+    pub fn as_config<E: Config>(&self) -> &Blob<E> {
+        // SAFETY: `Blob` is `#[repr(transparent)]` over `Vec<u8>`, as `PhantomData<fn() -> C>`
+        // is zero-sized with alignment 1, so `Blob<C>` and `Blob<E>` have identical layouts.
+        unsafe { &*(self as *const Blob<C> as *const Blob<E>) }
+    }
+
+    /// Encode the `Blob` to a string, using base-64 or whichever encoding `C` specifies
+    #[inline]
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Returns the exact length in bytes of the encoded form, including any padding, as
+    /// `encode_to_slice` requires
+    ///
+    /// This is computed without encoding for the base-64, base-32 and hex configs, so it can
+    /// size buffers, `Content-Length` headers or database columns cheaply.
+    #[inline]
+    pub fn encoded_len(&self) -> usize {
+        C::encoded_len(&self.data)
+    }
+
+    /// Encode the `Blob` into the start of `output`, returning the encoded length
+    ///
+    /// This does not allocate for the base-64 and hex configs. If `output` is shorter than
+    /// `encoded_len`, this fails with `ErrorKind::BufferTooSmall`, and `output` may have been
+    /// partially written.
+    pub fn encode_to_slice(&self, output: &mut [u8]) -> Result<usize, Error> {
+        C::encode_to_slice(&self.data, output).ok_or_else(|| {
+            Error::buffer_too_small(self.encoded_len(), any::type_name::<C>(), self.len())
+        })
+    }
+
+    /// Encode the `Blob` to a string as `encode_base64` does, in pieces across the rayon thread
+    /// pool
+    ///
+    /// Blobs larger than 64 KiB are split into whole blocks of the config and encoded in
+    /// parallel, while smaller ones and configs without a
+    /// [`BLOCK_LEN`](trait.Config.html#associatedconstant.BLOCK_LEN) are encoded on this thread.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn encode_base64_parallel(&self) -> String {
+        parallel::encode::<C>(&self.data)
+    }
+
+    /// Encode the `Blob` into `output`, replacing its contents but reusing its allocation
+    #[inline]
+    pub fn encode_base64_into(&self, output: &mut String) {
+        output.clear();
+
+        C::encode(&self.data, output);
+    }
+
+    /// Encode the `Blob`, appending the encoded form to the end of `output`
+    #[inline]
+    pub fn encode_base64_append(&self, output: &mut String) {
+        C::encode(&self.data, output);
+    }
+
+    /// Encodes the `Blob` to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data into a `Blob`
+    pub fn decode_base64<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        // perform as_ref here to only monomorphize the decoder once
+        let encoded = encoded.as_ref();
+
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        Blob::decode_checked(encoded, C::decode, false)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data as `decode_base64` would, in pieces across the
+    /// rayon thread pool
+    ///
+    /// Input longer than 64 KiB is split into whole blocks of the config and decoded in
+    /// parallel, with the first error in the input reported if there are several. Smaller
+    /// input, input containing whitespace, and configs without an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN) are
+    /// decoded on this thread.
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn decode_base64_parallel<T: AsRef<[u8]>>(encoded: T) -> Result<Blob<C>, Error> {
+        parallel::decode(encoded.as_ref())
+    }
+
+    /// Decode base-64 (or `C`-encoded) data as `decode_base64` would, reusing the allocation
+    /// of `encoded` for the decoded bytes
+    ///
+    /// The decoded bytes are always shorter than the input, so they are written over the front
+    /// of the input and adopted as the storage of the `Blob`. Configs without an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN), and input
+    /// containing whitespace, still decode into a new allocation.
+    #[inline]
+    pub fn decode_base64_in_place(encoded: String) -> Result<Blob<C>, Error> {
+        in_place::decode(encoded.into_bytes())
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from an owned buffer, as
+    /// `decode_base64_in_place` does
+    #[inline]
+    pub fn decode_owned(encoded: Vec<u8>) -> Result<Blob<C>, Error> {
+        in_place::decode(encoded)
+    }
+
+    /// Decodes `encoded` into a new `Blob` with `decode`, then checks it against
+    /// `C::REQUIRE_CANONICAL`, optionally ignoring whitespace
+    fn decode_checked<F>(encoded: &[u8], decode: F, lenient: bool) -> Result<Blob<C>, Error>
+    where
+        F: FnOnce(&[u8], &mut Vec<u8>) -> Result<(), base64::DecodeError>,
+    {
+        let blob = Blob::decode_using(encoded, any::type_name::<C>(), decode)?;
+
+        Blob::<C>::check_canonical(encoded, &blob.data, lenient)?;
+
+        Ok(blob)
+    }
+
+    /// Checks that `encoded` is exactly how `C` encodes `data`, if `C::REQUIRE_CANONICAL`
+    fn check_canonical(encoded: &[u8], data: &[u8], lenient: bool) -> Result<(), Error> {
+        if !C::REQUIRE_CANONICAL {
+            return Ok(());
+        }
+
+        Blob::<C>::compare_canonical(encoded, data, lenient)
+    }
+
+    /// Checks that `encoded` is exactly how `C` encodes `data`, optionally ignoring whitespace
+    fn compare_canonical(encoded: &[u8], data: &[u8], lenient: bool) -> Result<(), Error> {
+        // the canonical text is as sensitive as the input
+        #[cfg(feature = "zeroize")]
+        let mut canonical = zeroize::Zeroizing::new(String::new());
+        #[cfg(not(feature = "zeroize"))]
+        let mut canonical = String::new();
+
+        C::encode(data, &mut canonical);
+
+        let is_symbol = |c: &u8| !(lenient && c.is_ascii_whitespace());
+
+        let mut expected = canonical.bytes().filter(is_symbol);
+        let config = any::type_name::<C>();
+
+        for (i, &c) in encoded.iter().enumerate().filter(|&(_, c)| is_symbol(c)) {
+            if expected.next() != Some(c) {
+                return Err(Error::non_canonical(
+                    Some(i),
+                    Some(c),
+                    config,
+                    encoded.len(),
+                ));
+            }
+        }
+
+        match expected.next() {
+            Some(_) => Err(Error::non_canonical(None, None, config, encoded.len())),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that decoding `encoded` after `existing` bytes stays within `C::MAX_DECODED_LEN`,
+    /// if any, without decoding it
+    ///
+    /// The decoded length is bounded from the encoded length alone where possible, and otherwise
+    /// validated without allocating, except for base-58.
+    fn check_max_len(encoded: &[u8], existing: usize) -> Result<(), Error> {
+        match C::MAX_DECODED_LEN {
+            Some(limit) => Blob::<C>::check_limit(encoded, limit, existing),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that decoding `encoded` after `existing` bytes stays within `limit` bytes, as
+    /// `check_max_len` does for `C::MAX_DECODED_LEN`
+    pub(crate) fn check_limit(encoded: &[u8], limit: usize, existing: usize) -> Result<(), Error> {
+        let max = limit.saturating_sub(existing);
+        let config = any::type_name::<C>();
+        let too_long = || Error::too_long(limit, config, encoded.len());
+
+        match C::decoded_len_bounds(encoded.len()) {
+            (min, _) if min > max => return Err(too_long()),
+            (_, max_len) if max_len <= max => return Ok(()),
+            _ => {}
+        }
+
+        match C::validate(encoded) {
+            Ok(len) if len > max => Err(too_long()),
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::new(err, config, encoded.len())),
+        }
+    }
+
+    /// Decodes `encoded` into a new `Blob` with `decode`, attributing errors to `config`
+    fn decode_using<F>(encoded: &[u8], config: &'static str, decode: F) -> Result<Blob<C>, Error>
+    where
+        F: FnOnce(&[u8], &mut Vec<u8>) -> Result<(), base64::DecodeError>,
+    {
+        let mut blob = Blob::new();
+
+        decode(encoded, &mut blob.data).map_err(|err| Error::new(err, config, encoded.len()))?;
+
+        match C::MAX_DECODED_LEN {
+            Some(max) if blob.data.len() > max => Err(Error::too_long(max, config, encoded.len())),
+            _ => Ok(blob),
+        }
+    }
+
+    /// Decodes `encoded` as `decode_base64` would, but into the start of `output`, returning the
+    /// decoded length, or `None` if `C` cannot decode into a slice or `output` is too short
+    pub(crate) fn decode_to_slice(
+        encoded: &[u8],
+        output: &mut [u8],
+    ) -> Option<Result<usize, Error>> {
+        let result = C::decode_to_slice(encoded, output)?;
+
+        // checked afterwards, as `output` bounds the work, but reported first as with `Blob`
+        if let Err(err) = Blob::<C>::check_max_len(encoded, 0) {
+            return Some(Err(err));
+        }
+
+        let len = match result {
+            Ok(len) => len,
+            Err(err) => return Some(Err(Error::new(err, any::type_name::<C>(), encoded.len()))),
+        };
+
+        Some(Blob::<C>::check_canonical(encoded, &output[..len], false).map(|_| len))
+    }
+
+    /// Decode base-64 (or `C`-encoded) data into a `Blob`, only accepting the exact form
+    /// that encoding produces
+    ///
+    /// Unlike `FromStr`, no surrounding whitespace is trimmed, and unlike `decode_base64`,
+    /// the padding, letter case and line breaks must match those of `C`, so padded configs
+    /// reject unpadded input and vice versa, and `Hex` rejects uppercase digits. The decoded
+    /// bytes are encoded again to check this, failing with `ErrorKind::NonCanonical`.
+    pub fn decode_base64_strict<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        let blob = Blob::decode_using(encoded, any::type_name::<C>(), C::decode_strict)?;
+
+        Blob::<C>::compare_canonical(encoded, &blob.data, false)?;
+
+        Ok(blob)
+    }
+
+    /// Checks that base-64 (or `C`-encoded) data is well-formed, returning its decoded length
+    ///
+    /// This accepts exactly what `decode_base64` does, but without allocating for the
+    /// decoded bytes, for every config but `Base58`.
+    pub fn validate<T>(encoded: T) -> Result<usize, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        // a canonical encoding can only be checked against the decoded bytes
+        if C::REQUIRE_CANONICAL {
+            return Blob::<C>::decode_base64(encoded).map(|blob| blob.len());
+        }
+
+        C::validate(encoded).map_err(|err| Error::new(err, any::type_name::<C>(), encoded.len()))
+    }
+
+    /// Encode the `Blob` to a lowercase hexadecimal string, regardless of `C`
+    #[inline]
+    pub fn to_hex(&self) -> String {
+        self.as_config::<Hex>().encode_base64()
+    }
+
+    /// Decode case-insensitive hexadecimal data into a `Blob`, regardless of `C`
+    #[inline]
+    pub fn from_hex<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::<Hex>::decode_base64(encoded).map(Blob::with_config)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data into a `Blob`, skipping any ASCII whitespace
+    ///
+    /// This accepts base-64 split across lines or indented, such as from certificates, emails
+    /// or YAML literal blocks. For base-64 configs, no cleaned copy of the input is allocated.
+    pub fn decode_base64_lenient<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::decode_checked(encoded.as_ref(), C::decode_lenient, true)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from a reader, ignoring ASCII whitespace as
+    /// `decode_base64_lenient` does
+    ///
+    /// The text is consumed incrementally for configs with an
+    /// [`ENCODED_BLOCK_LEN`](trait.Config.html#associatedconstant.ENCODED_BLOCK_LEN), so only
+    /// the decoded bytes are held in memory. Other configs read all of the text first.
+    /// Decoding errors are returned as `io::ErrorKind::InvalidData`, wrapping an `Error`.
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader<R: io::Read>(reader: R) -> io::Result<Blob<C>> {
+        stream::decode_from_reader(reader)
+    }
+
+    /// Decode base-64 (or `C`-encoded) data into a `Blob`, accepting input with or without padding
+    ///
+    /// Encoding still uses the canonical padding of `C`. The built-in base-64 configs already
+    /// decode regardless of padding, but this is guaranteed for every config, such as `Base32`.
+    pub fn decode_base64_any_padding<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::decode_checked(encoded.as_ref(), C::decode_any_padding, false)
+    }
+
+    /// Decode base-64 encoded data from an iterator of bytes into a `Blob`
+    ///
+    /// The encoded bytes are collected into a temporary buffer before decoding,
+    /// so this is not zero-copy.
+    pub fn decode_base64_from_iter<I>(iter: I) -> Result<Blob<C>, Error>
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let encoded: Vec<u8> = iter.into_iter().collect();
+
+        Blob::decode_base64(encoded)
+    }
+
+    /// Encode the `Blob` to a base-58 string, regardless of `C`
+    #[inline]
+    pub fn encode_base58(&self) -> String {
+        self.as_config::<Base58>().encode_base64()
+    }
+
+    /// Decode base-58 data into a `Blob`, regardless of `C`
+    #[inline]
+    pub fn decode_base58<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::<Base58>::decode_base64(encoded).map(Blob::with_config)
+    }
+
+    /// Encode the `Blob` to a base-64 string using a runtime config, regardless of `C`
+    #[inline]
+    pub fn encode_with(&self, config: &DynConfig) -> String {
+        let mut encoded = String::new();
+
+        config.encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Decode base-64 data into a `Blob` using a runtime config, regardless of `C`
+    pub fn decode_with<T>(encoded: T, config: &DynConfig) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        Blob::decode_using(
+            encoded.as_ref(),
+            any::type_name::<DynConfig>(),
+            |input, output| config.decode(input, output),
+        )
+    }
+
+    /// Decode data of an unknown encoding into a `Blob`, returning the encoding that matched
+    ///
+    /// Input consisting solely of an even number of hex digits is decoded as hex, otherwise
+    /// standard base-64 then URL-safe base-64 are attempted, in that order. Note that short
+    /// inputs can be valid in several encodings, in which case the first match wins.
+    pub fn decode_auto<T>(encoded: T) -> Result<(DetectedEncoding, Blob<C>), Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        let is_hex = !encoded.is_empty()
+            && encoded.len().is_multiple_of(2)
+            && encoded.iter().all(u8::is_ascii_hexdigit);
+
+        if is_hex {
+            if let Ok(blob) = Blob::from_hex(encoded) {
+                return Ok((DetectedEncoding::Hex, blob));
+            }
+        }
+
+        match Blob::<Standard>::decode_base64(encoded) {
+            Ok(blob) => Ok((DetectedEncoding::Standard, blob.with_config())),
+            Err(err) => match Blob::<UrlSafe>::decode_base64(encoded) {
+                Ok(blob) => Ok((DetectedEncoding::UrlSafe, blob.with_config())),
+                // prefer reporting the URL-safe error if that's what the input looks like
+                Err(url_err) => match err.byte() {
+                    Some(b'-') | Some(b'_') => Err(url_err),
+                    _ => Err(err),
+                },
+            },
+        }
+    }
+
+    /// Encode the `Blob` with the given multibase encoding, prefixed by its identifying character
+    pub fn encode_multibase(&self, base: Multibase) -> String {
+        let mut encoded = String::new();
+
+        base.encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Decode multibase data into a `Blob`, dispatching on its prefix character
+    ///
+    /// Returns the encoding that was used along with the decoded `Blob`.
+    pub fn decode_multibase<T>(encoded: T) -> Result<(Multibase, Blob<C>), Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+        let mut blob = Blob::new();
+
+        Multibase::decode(encoded, &mut blob.data)
+            .map(|base| (base, blob))
+            .map_err(|err| Error::new(err, any::type_name::<Multibase>(), encoded.len()))
+    }
+
+    /// Encode the `Blob` to a Z85 string, regardless of `C`
+    ///
+    /// Returns `None` if the length of the `Blob` is not a multiple of 4.
+    pub fn encode_z85(&self) -> Option<String> {
+        let mut encoded = String::new();
+
+        base85::encode_z85(&self.data, &mut encoded).map(|_| encoded)
+    }
+
+    /// Decode Z85 data into a `Blob`, regardless of `C`
+    ///
+    /// The length of `encoded` must be a multiple of 5.
+    pub fn decode_z85<T>(encoded: T) -> Result<Blob<C>, Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+
+        Blob::decode_using(encoded, "Z85", base85::decode_z85)
+    }
+
+    /// Encode the `Blob` to a Base58Check string, appending a 4-byte double SHA-256 checksum
+    #[cfg(feature = "sha2")]
+    pub fn encode_base58check(&self) -> String {
+        let mut data = Vec::with_capacity(self.data.len() + 4);
+
+        data.extend_from_slice(&self.data);
+        data.extend_from_slice(&double_sha256_checksum(&self.data));
+
+        Blob::<Base58>::from_vec(data).encode_base64()
+    }
+
+    /// Decode Base58Check data into a `Blob`, verifying and removing the checksum
+    #[cfg(feature = "sha2")]
+    pub fn decode_base58check<T>(encoded: T) -> Result<Blob<C>, ChecksumError>
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut blob = Blob::<C>::decode_base58(encoded)?;
+
+        let len = blob
+            .data
+            .len()
+            .checked_sub(4)
+            .ok_or(ChecksumError::MissingChecksum)?;
+
+        if blob.data[len..] != double_sha256_checksum(&blob.data[..len]) {
+            return Err(ChecksumError::Mismatch);
+        }
+
+        blob.data.truncate(len);
+
+        Ok(blob)
+    }
+
+    /// Encode the `Blob` to a string as `encode_base64` would, after appending the same 4-byte
+    /// checksum as `encode_base58check`
+    ///
+    /// Decoding with `decode_base64_check` then catches mistyped or truncated text, rather than
+    /// silently returning different bytes.
+    #[cfg(feature = "sha2")]
+    pub fn encode_base64_check(&self) -> String {
+        let mut data = Vec::with_capacity(self.data.len() + 4);
+
+        data.extend_from_slice(&self.data);
+        data.extend_from_slice(&double_sha256_checksum(&self.data));
+
+        Blob::<C>::from_vec(data).encode_base64()
+    }
+
+    /// Decode data from `encode_base64_check` into a `Blob`, verifying and removing the checksum
+    #[cfg(feature = "sha2")]
+    pub fn decode_base64_check<T>(encoded: T) -> Result<Blob<C>, ChecksumError>
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut blob = Blob::<C>::decode_base64(encoded)?;
+
+        let len = blob
+            .data
+            .len()
+            .checked_sub(4)
+            .ok_or(ChecksumError::MissingChecksum)?;
 
-extern crate base64;
-extern crate serde;
+        if blob.data[len..] != double_sha256_checksum(&blob.data[..len]) {
+            return Err(ChecksumError::Mismatch);
+        }
 
-use std::borrow::{Borrow, BorrowMut};
-use std::fmt::{self, Display};
-use std::hash::{Hash, Hasher};
-use std::io::{self, Write};
-use std::iter::{Extend, FromIterator, IntoIterator};
-use std::marker::PhantomData;
-use std::ops::{Deref, DerefMut};
-use std::slice::{Iter, IterMut};
-use std::str::FromStr;
-use std::vec::IntoIter;
+        blob.data.truncate(len);
 
-/// Trait used for statically typed Blob encoding configs
-pub trait Config: Send + Sync {
-    /// Associated base-64 config
-    const CONFIG: base64::Config;
-}
+        Ok(blob)
+    }
 
-macro_rules! impl_configs {
-    ($($(#[$($attrs:tt)*])* $name:ident: $config:ident,)*) => {
-        $(
-            $(#[$($attrs)*])*
-            pub enum $name {}
+    /// Encode the `Blob` as OpenPGP ASCII armor with the given label, regardless of `C`
+    ///
+    /// The label is the text between `BEGIN` and the dashes, such as `PGP PUBLIC KEY BLOCK`.
+    /// The body is base-64 in lines of 64 characters, followed by the CRC-24 checksum line of
+    /// RFC 4880, which GnuPG reads and writes.
+    pub fn encode_armor(&self, label: &str) -> String {
+        let mut encoded = String::new();
 
-            impl Config for $name {
-                const CONFIG: base64::Config = base64::$config;
-            }
-        )*
+        armor::encode(label, &self.data, &mut encoded);
+
+        encoded
     }
-}
 
-impl_configs! {
-    /// As per `crypt(3)` requirements
-    Crypt: CRYPT,
+    /// Decode the first OpenPGP ASCII armor block in `encoded` into a `Blob`, regardless of `C`
+    ///
+    /// Returns the label of the block along with the decoded `Blob`. Text around the block and
+    /// armor headers such as `Version: ...` are ignored. The CRC-24 checksum is verified if
+    /// present, but not required.
+    #[inline]
+    pub fn decode_armor<T>(encoded: T) -> Result<(String, Blob<C>), ArmorError>
+    where
+        T: AsRef<[u8]>,
+    {
+        armor::decode(encoded.as_ref()).map(|(label, blob)| (label, blob.with_config()))
+    }
 
-    /// Standard character set with padding.
-    Standard: STANDARD,
+    /// Encode the `Blob` as PEM with the given label, regardless of `C`
+    ///
+    /// The label is the text between `BEGIN` and the dashes, such as `CERTIFICATE` or
+    /// `PRIVATE KEY`. The body is base-64 in lines of 64 characters, as RFC 7468 requires.
+    pub fn to_pem(&self, label: &str) -> String {
+        let mut encoded = String::new();
 
-    /// Standard character set without padding.
-    StandardNoPad: STANDARD_NO_PAD,
+        pem::encode(label, &self.data, &mut encoded);
 
-    /// URL-safe character set with padding
-    UrlSafe: URL_SAFE,
+        encoded
+    }
 
-    /// URL-safe character set without padding
-    UrlSafeNoPad: URL_SAFE_NO_PAD,
-}
+    /// Decode the first PEM section in `encoded` into a `Blob`, regardless of `C`
+    ///
+    /// Returns the label of the section along with the decoded `Blob`. Text around the section,
+    /// line breaks anywhere in the body and RFC 1421 header lines such as `Proc-Type: ...` are
+    /// ignored.
+    #[inline]
+    pub fn from_pem<T>(encoded: T) -> Result<(String, Blob<C>), PemError>
+    where
+        T: AsRef<[u8]>,
+    {
+        pem::decode(encoded.as_ref()).map(|(label, blob)| (label, blob.with_config()))
+    }
 
-/// Blob structure containing binary data
-///
-/// Interally, the blob is stored as a plain `Vec<u8>`, and some
-/// methods are exposed from that. If you need full access to the
-/// underlying `Vec`, use `borrow()` or `borrow_mut()`
-pub struct Blob<C: Config = Standard> {
-    data: Vec<u8>,
-    _config: PhantomData<C>,
-}
+    /// Decode every PEM section in `encoded`, such as a certificate chain, in order
+    ///
+    /// Each section is decoded as by `from_pem`. Returns an empty `Vec` if there are none.
+    pub fn from_pem_bundle<T>(encoded: T) -> Result<Vec<(String, Blob<C>)>, PemError>
+    where
+        T: AsRef<[u8]>,
+    {
+        pem::decode_bundle(encoded.as_ref()).map(|sections| {
+            sections
+                .into_iter()
+                .map(|(label, blob)| (label, blob.with_config()))
+                .collect()
+        })
+    }
 
-impl<C: Config> Default for Blob<C> {
+    /// Encode the `Blob` as a `data:` URL with the given media type, regardless of `C`
+    ///
+    /// The result is `data:<media_type>;base64,<payload>`, ready to inline into HTML, CSS or
+    /// JSON. The media type is written as given, such as `image/png`.
+    pub fn to_data_url(&self, media_type: &str) -> String {
+        let mut encoded = String::new();
+
+        data_url::encode(media_type, &self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Decode a base-64 or percent-encoded `data:` URL into a `Blob`, regardless of `C`
+    ///
+    /// Returns the media type, including any parameters such as `charset`, along with the
+    /// decoded `Blob`. A URL without a media type returns `text/plain;charset=US-ASCII`, as
+    /// RFC 2397 specifies.
     #[inline]
-    fn default() -> Self {
-        Blob {
-            data: Vec::new(),
-            _config: PhantomData,
+    pub fn from_data_url<T>(url: T) -> Result<(String, Blob<C>), DataUrlError>
+    where
+        T: AsRef<[u8]>,
+    {
+        data_url::decode(url.as_ref()).map(|(media_type, blob)| (media_type, blob.with_config()))
+    }
+
+    /// Decodes some base-64 (or `C`-encoded) data and appends it to the `Blob`
+    ///
+    /// On error, the `Blob` is left unchanged.
+    #[inline]
+    pub fn append_base64<T>(&mut self, encoded: T) -> Result<(), Error>
+    where
+        T: AsRef<[u8]>,
+    {
+        let encoded = encoded.as_ref();
+        let len = self.data.len();
+
+        Blob::<C>::check_max_len(encoded, len)?;
+
+        let config = any::type_name::<C>();
+
+        if let Err(err) = C::decode(encoded, &mut self.data) {
+            self.data.truncate(len);
+
+            return Err(Error::new(err, config, encoded.len()));
         }
+
+        let result = match C::MAX_DECODED_LEN {
+            Some(max) if self.data.len() > max => Err(Error::too_long(max, config, encoded.len())),
+            _ => Blob::<C>::check_canonical(encoded, &self.data[len..], false),
+        };
+
+        result.inspect_err(|_| self.data.truncate(len))
     }
-}
 
-impl<C: Config> Blob<C> {
-    /// Create a new empty `Blob`
+    /// Appends PKCS#7 padding so the length of the `Blob` becomes a multiple of `block`
+    ///
+    /// A full block of padding is added if the length is already a multiple of `block`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block` is not in the range `1..=255`
+    pub fn pad_pkcs7(&mut self, block: usize) {
+        assert!((1..=255).contains(&block), "Invalid block size {}", block);
+
+        let padding = block - self.data.len() % block;
+
+        self.data.resize(self.data.len() + padding, padding as u8);
+    }
+
+    /// Validates and removes PKCS#7 padding for the given `block` size
+    ///
+    /// The `Blob` is left untouched if the padding is invalid.
+    pub fn unpad_pkcs7(&mut self, block: usize) -> Result<(), PadError> {
+        if !(1..=255).contains(&block) {
+            return Err(PadError::InvalidBlockSize(block));
+        }
+
+        let len = self.data.len();
+
+        if len == 0 || !len.is_multiple_of(block) {
+            return Err(PadError::InvalidLength);
+        }
+
+        let padding = self.data[len - 1] as usize;
+
+        if padding == 0
+            || padding > block
+            || self.data[len - padding..]
+                .iter()
+                .any(|&b| b as usize != padding)
+        {
+            return Err(PadError::InvalidPadding);
+        }
+
+        self.data.truncate(len - padding);
+
+        Ok(())
+    }
+
+    /// Returns `true` if the bytes equal `other`, in time that depends only on their lengths
+    ///
+    /// Unlike `==`, this does not stop at the first differing byte, so comparing MACs or tokens
+    /// does not reveal how much of them matched. The lengths themselves are not hidden.
     #[inline]
-    pub fn new() -> Blob<C> {
-        Blob::default()
+    pub fn ct_eq<T: AsRef<[u8]> + ?Sized>(&self, other: &T) -> bool {
+        constant_time_eq(&self.data, other.as_ref())
     }
 
-    /// Create a `Blob` from an underlying `Vec`
+    /// Hash the bytes with the RustCrypto digest `D`, such as `sha2::Sha256`
+    #[cfg(feature = "digest")]
     #[inline]
-    pub fn from_vec(vec: Vec<u8>) -> Blob<C> {
-        Blob {
-            data: vec,
-            _config: PhantomData,
-        }
+    pub fn hash<D: digest::Digest>(&self) -> digest::Output<D> {
+        D::digest(&self.data)
     }
 
-    /// Create a new `Blob` with the given capacity
+    /// Feeds the bytes into `digest`, to hash them along with other data
+    #[cfg(feature = "digest")]
     #[inline]
-    pub fn with_capacity(capacity: usize) -> Blob<C> {
-        Blob::from_vec(Vec::with_capacity(capacity))
+    pub fn hash_into<D: digest::Digest>(&self, digest: &mut D) {
+        digest.update(&self.data);
     }
 
-    /// Returns the number of bytes the `Blob` can hold without reallocating.
+    /// Returns `true` if hashing the bytes with `D` gives `expected`, compared in constant time
+    #[cfg(feature = "digest")]
     #[inline]
-    pub fn capacity(&self) -> usize {
-        self.data.capacity()
+    pub fn verify_hash<D: digest::Digest>(&self, expected: &[u8]) -> bool {
+        constant_time_eq(&self.hash::<D>(), expected)
     }
 
-    /// Reserves capacity for at least additional more bytes to be inserted in the given `Blob`
+    /// Computes the MAC `M` of the bytes under `key`, such as with `hmac::Hmac<sha2::Sha256>`
+    ///
+    /// Fails only if `M` does not accept keys of that length, which HMAC never does.
+    #[cfg(feature = "hmac")]
+    pub fn hmac<M: digest::Mac + digest::KeyInit>(
+        &self,
+        key: &[u8],
+    ) -> Result<digest::Output<M>, digest::InvalidLength> {
+        let mut mac = <M as digest::KeyInit>::new_from_slice(key)?;
+
+        digest::Mac::update(&mut mac, &self.data);
+
+        Ok(mac.finalize().into_bytes())
+    }
+
+    /// Returns the SHA-256 fingerprint of the bytes
+    ///
+    /// Use `to_hex` on the result for the usual hexadecimal form.
+    #[cfg(feature = "sha2")]
     #[inline]
-    pub fn reserve(&mut self, additional: usize) {
-        self.data.reserve(additional)
+    pub fn sha256(&self) -> FixedBlob<C, 32> {
+        use sha2::{Digest, Sha256};
+
+        FixedBlob::new(Sha256::digest(&self.data).into())
     }
 
-    /// Use a different encoding configuration for the `Blob`
-    #[inline(always)]
-    pub fn with_config<E: Config>(self) -> Blob<E> {
-        Blob {
-            data: self.data,
-            _config: PhantomData,
-        }
+    /// Returns the BLAKE3 fingerprint of the bytes
+    ///
+    /// Use `to_hex` on the result for the usual hexadecimal form.
+    #[cfg(feature = "blake3")]
+    #[inline]
+    pub fn blake3(&self) -> FixedBlob<C, 32> {
+        FixedBlob::new(*blake3::hash(&self.data).as_bytes())
+    }
+
+    /// Returns the multihash of the bytes, the varint code of `hash` and digest length followed
+    /// by the digest
+    #[cfg(feature = "cid")]
+    pub fn multihash(&self, hash: Multihash) -> Blob<C> {
+        let mut blob = Blob::new();
+
+        cid::multihash(hash, &self.data, &mut blob.data);
+
+        blob
     }
 
-    /// Encode the `Blob` to a base-64 string
+    /// Returns `true` if `expected` is a multihash of the bytes, compared in constant time
+    ///
+    /// Returns `false` for hash functions not listed in `Multihash`.
+    #[cfg(feature = "cid")]
     #[inline]
-    pub fn encode_base64(&self) -> String {
-        base64::encode_config(&self.data, C::CONFIG)
+    pub fn verify_multihash(&self, expected: &[u8]) -> bool {
+        cid::verify_multihash(expected, &self.data)
+    }
+
+    /// Returns the CIDv1 of the bytes for the given content type, hashed with SHA-256
+    ///
+    /// This is the canonical string form, lowercase base-32 starting with `b`, regardless of
+    /// `C`, so it can be used as a key to deduplicate blobs by content.
+    #[cfg(feature = "cid")]
+    #[inline]
+    pub fn cid(&self, codec: Multicodec) -> String {
+        cid::cid(codec, Multihash::Sha2_256, &self.data)
+    }
+
+    /// Returns the CIDv1 of the bytes for the given content type and hash function
+    #[cfg(feature = "cid")]
+    #[inline]
+    pub fn cid_with(&self, codec: Multicodec, hash: Multihash) -> String {
+        cid::cid(codec, hash, &self.data)
+    }
+
+    /// Returns `true` if `expected` is a CID string addressing the bytes
+    ///
+    /// This accepts CIDv0 strings starting with `Qm` and CIDv1 strings in any multibase, with
+    /// any content type and any hash function listed in `Multihash`.
+    #[cfg(feature = "cid")]
+    #[inline]
+    pub fn verify_cid(&self, expected: &str) -> bool {
+        cid::verify_cid(expected, &self.data)
+    }
+
+    /// Returns the MIME type of the bytes sniffed from their magic number, such as `image/png`
+    ///
+    /// Returns `None` if the format is not recognized. Only the first few bytes are inspected,
+    /// so this confirms that an upload is of its claimed type, not that it is well-formed.
+    #[cfg(feature = "infer")]
+    #[inline]
+    pub fn detect_mime(&self) -> Option<&'static str> {
+        infer::get(&self.data).map(|kind| kind.mime_type())
+    }
+
+    /// Returns the usual file extension of the bytes sniffed from their magic number, without
+    /// the leading dot, such as `png`
+    #[cfg(feature = "infer")]
+    #[inline]
+    pub fn detect_extension(&self) -> Option<&'static str> {
+        infer::get(&self.data).map(|kind| kind.extension())
+    }
+
+    /// Returns the CRC-32 checksum of the bytes, as computed by zlib, gzip and PNG
+    #[cfg(feature = "crc")]
+    #[inline]
+    pub fn crc32(&self) -> u32 {
+        CRC_32.checksum(&self.data)
+    }
+
+    /// Returns the CRC-32C (Castagnoli) checksum of the bytes, as used by S3, GCS and iSCSI
+    #[cfg(feature = "crc")]
+    #[inline]
+    pub fn crc32c(&self) -> u32 {
+        CRC_32C.checksum(&self.data)
+    }
+
+    /// Returns the CRC-64/NVME checksum of the bytes, as used by S3 and NVMe
+    #[cfg(feature = "crc")]
+    #[inline]
+    pub fn crc64nvme(&self) -> u64 {
+        CRC_64_NVME.checksum(&self.data)
+    }
+
+    /// Consume self and return the inner `Vec<u8>`
+    #[inline]
+    pub fn into_vec(mut self) -> Vec<u8> {
+        mem::take(&mut self.data)
+    }
+
+    /// Read the raw bytes of the file at `path` into a `Blob`
+    ///
+    /// Errors include the path of the file.
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Blob<C>> {
+        file::read(path.as_ref(), None, any::type_name::<C>()).map(Blob::from_vec)
     }
 
-    /// Encodes the `Blob` as base-64 to an `io::Writer`, avoiding intermediate allocations
-    pub fn encode_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
-        let mut encoder = base64::write::EncoderWriter::new(&mut writer, C::CONFIG);
+    /// Read the raw bytes of the file at `path` into a `Blob`, failing if it is larger than
+    /// `max` bytes
+    ///
+    /// Files that are too large fail before being read with `io::ErrorKind::InvalidData`,
+    /// wrapping an `Error` of `ErrorKind::TooLong`.
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn from_file_limited<P: AsRef<Path>>(path: P, max: usize) -> io::Result<Blob<C>> {
+        file::read(path.as_ref(), Some(max), any::type_name::<C>()).map(Blob::from_vec)
+    }
+
+    /// Write the raw bytes to the file at `path`, creating or truncating it
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        file::write(path.as_ref(), &self.data, false)
+    }
 
-        encoder.write_all(&self.data)
+    /// Write the raw bytes to the file at `path` atomically
+    ///
+    /// The bytes are written and synced to a new temporary file in the same directory, which is
+    /// then renamed over `path` before syncing the directory, so readers never see a partially
+    /// written file, and concurrent writes to the same path never share a temporary file.
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn write_to_file_atomic<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        file::write(path.as_ref(), &self.data, true)
     }
 
-    /// Decode base-64 encoded data into a `Blob`
-    pub fn decode_base64<T>(encoded: T) -> Result<Blob<C>, base64::DecodeError>
+    /// Read the raw bytes of the file at `path` into a `Blob`, as `from_file` does
+    ///
+    /// The file is read on tokio's blocking thread pool, as with `tokio::fs`, so the future must be
+    /// polled within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn from_file_async<P>(path: P) -> impl Future<Output = io::Result<Blob<C>>>
     where
-        T: AsRef<[u8]>,
+        P: AsRef<Path>,
     {
-        // perform as_ref here to only monomorphize the decoder once
-        base64::decode_config(encoded.as_ref(), C::CONFIG).map(Blob::from_vec)
+        let path = path.as_ref().to_owned();
+
+        async_io::read_file(path, None, any::type_name::<C>(), Blob::from_vec)
     }
 
-    /// Decodes some base-64 data and appends it to the `Blob`
+    /// Read the raw bytes of the file at `path` into a `Blob`, failing if it is larger than
+    /// `max` bytes, as `from_file_limited` does
+    ///
+    /// As with `from_file_async`, the future must be polled within a tokio runtime.
+    #[cfg(feature = "tokio")]
     #[inline]
-    pub fn append_base64<T>(&mut self, encoded: T) -> Result<(), base64::DecodeError>
+    pub fn from_file_limited_async<P>(
+        path: P,
+        max: usize,
+    ) -> impl Future<Output = io::Result<Blob<C>>>
     where
-        T: AsRef<[u8]>,
+        P: AsRef<Path>,
     {
-        // perform as_ref here to only monomorphize the decoder once
-        base64::decode_config_buf(encoded.as_ref(), C::CONFIG, &mut self.data)
+        let path = path.as_ref().to_owned();
+
+        async_io::read_file(path, Some(max), any::type_name::<C>(), Blob::from_vec)
     }
 
-    /// Consume self and return the inner `Vec<u8>`
+    /// Write the raw bytes to the file at `path`, as `write_to_file` does
+    ///
+    /// The bytes are copied and written on tokio's blocking thread pool, as `tokio::fs::write`
+    /// does, so the future must be polled within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn write_to_file_async<P>(&self, path: P) -> impl Future<Output = io::Result<()>>
+    where
+        P: AsRef<Path>,
+    {
+        async_io::write_file(path.as_ref().to_owned(), self.data.clone(), false)
+    }
+
+    /// Write the raw bytes to the file at `path` atomically, as `write_to_file_atomic` does
+    ///
+    /// As with `write_to_file_async`, the future must be polled within a tokio runtime.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn write_to_file_atomic_async<P>(&self, path: P) -> impl Future<Output = io::Result<()>>
+    where
+        P: AsRef<Path>,
+    {
+        async_io::write_file(path.as_ref().to_owned(), self.data.clone(), true)
+    }
+
+    /// Returns a reader over the bytes, implementing `Read`, `BufRead` and `Seek`
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn reader(&self) -> BlobReader<'_, C> {
+        BlobReader::new(self)
+    }
+
+    /// Returns a reader over the encoded form, which is produced lazily as it is read
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_reader(&self) -> EncodeReader<'_, C> {
+        EncodeReader::new(self)
+    }
+
+    /// Returns an iterator over encoded chunks of at most `len` bytes each, as whole blocks
+    ///
+    /// Every chunk decodes on its own, and decoding them in order reproduces the `Blob`, so a
+    /// large blob can be uploaded in separately valid parts. See `EncodeChunks::new`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero.
+    #[inline]
+    pub fn encode_chunks(&self, len: usize) -> EncodeChunks<'_, C> {
+        EncodeChunks::new(self, len)
+    }
+
+    /// Returns a `Stream` of the encoded form, in chunks of at most `chunk_size` bytes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    #[cfg(feature = "futures")]
+    #[inline]
+    pub fn encode_stream(&self, chunk_size: usize) -> EncodeStream<'_, C> {
+        EncodeStream::new(self, chunk_size)
+    }
+
+    /// Encodes the bytes to a tokio `AsyncWrite`, as `encode_to` does to an `io::Write`
+    ///
+    /// The text is encoded lazily as the writer accepts it, without flushing the writer.
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn encode_to_async<'a, W>(&'a self, writer: W) -> impl Future<Output = io::Result<()>> + 'a
+    where
+        W: tokio::io::AsyncWrite + Unpin + 'a,
+    {
+        async_io::EncodeTo::new(self, async_io::TokioIo(writer))
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from a tokio `AsyncRead`, as `decode_from_reader`
+    /// does from an `io::Read`
+    #[cfg(feature = "tokio")]
+    #[inline]
+    pub fn decode_from_async<R>(reader: R) -> impl Future<Output = io::Result<Blob<C>>>
+    where
+        R: tokio::io::AsyncRead + Unpin,
+    {
+        async_io::DecodeFrom::new(async_io::TokioIo(reader))
+    }
+
+    /// Encodes the bytes to a `futures-io` `AsyncWrite`, as `encode_to_async` does for tokio
+    #[cfg(feature = "futures-io")]
+    #[inline]
+    pub fn encode_to_futures<'a, W>(
+        &'a self,
+        writer: W,
+    ) -> impl Future<Output = io::Result<()>> + 'a
+    where
+        W: futures_io::AsyncWrite + Unpin + 'a,
+    {
+        async_io::EncodeTo::new(self, async_io::FuturesIo(writer))
+    }
+
+    /// Decode base-64 (or `C`-encoded) data from a `futures-io` `AsyncRead`, as
+    /// `decode_from_async` does for tokio
+    #[cfg(feature = "futures-io")]
+    #[inline]
+    pub fn decode_from_futures<R>(reader: R) -> impl Future<Output = io::Result<Blob<C>>>
+    where
+        R: futures_io::AsyncRead + Unpin,
+    {
+        async_io::DecodeFrom::new(async_io::FuturesIo(reader))
+    }
+
+    /// Copy the bytes of a JavaScript `Uint8Array` into a new `Blob`
+    ///
+    /// This is not a `From` impl, which would overlap with `From<T: Into<Vec<u8>>>`.
+    #[cfg(feature = "wasm")]
     #[inline]
-    pub fn into_vec(self) -> Vec<u8> {
-        self.data
+    pub fn from_uint8array(array: &js_sys::Uint8Array) -> Blob<C> {
+        wasm::from_uint8array(array)
+    }
+
+    /// Copy the bytes into a new JavaScript `Uint8Array`
+    #[cfg(feature = "wasm")]
+    #[inline]
+    pub fn to_uint8array(&self) -> js_sys::Uint8Array {
+        wasm::to_uint8array(&self.data)
+    }
+
+    /// Read the bytes of a JavaScript `Blob`, such as a `File` from an `<input>`, into a new
+    /// `Blob`
+    ///
+    /// The future resolves once the browser has read the whole `Blob`, and must be polled by
+    /// `wasm-bindgen-futures` or another executor running on the JavaScript event loop.
+    #[cfg(feature = "wasm")]
+    #[inline]
+    pub fn from_js_blob(blob: &web_sys::Blob) -> impl Future<Output = Result<Blob<C>, JsValue>> {
+        wasm::FromJsBlob::new(blob)
+    }
+
+    /// Copy the bytes into a new JavaScript `Blob`, for downloads or `fetch` bodies
+    #[cfg(feature = "wasm")]
+    #[inline]
+    pub fn to_js_blob(&self) -> Result<web_sys::Blob, JsValue> {
+        wasm::to_js_blob(&self.data)
+    }
+
+    /// Borrow the bytes as a `BlobRef`, which encodes and serializes as this `Blob` would
+    #[inline]
+    pub fn as_blob_ref(&self) -> BlobRef<'_, C> {
+        BlobRef::new(&self.data)
+    }
+
+    /// Wrap the `Blob` to serialize it with a CBOR expected-encoding tag in binary formats
+    #[inline]
+    #[cfg(feature = "serde")]
+    pub fn tagged(self) -> Tagged<C> {
+        Tagged(self)
     }
 }
 
+/// Decodes a `Blob` from a string, ignoring leading and trailing ASCII whitespace
+///
+/// Use [`Blob::decode_base64_strict`](struct.Blob.html#method.decode_base64_strict) to reject
+/// any whitespace instead.
 impl<C: Config> FromStr for Blob<C> {
-    type Err = base64::DecodeError;
+    type Err = Error;
 
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Blob::decode_base64(s)
+        let (offset, trimmed) = whitespace::trim(s);
+
+        // offsets are reported relative to the untrimmed string
+        Blob::decode_base64(trimmed).map_err(|err| err.within(offset, s.len()))
     }
 }
 
@@ -204,7 +2775,7 @@ impl<C: Config> fmt::Debug for Blob<C> {
 
 impl<C: Config> Display for Blob<C> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        base64::display::Base64Display::with_config(&self.data, C::CONFIG).fmt(f)
+        C::fmt(&self.data, f)
     }
 }
 
@@ -215,6 +2786,7 @@ impl<C: Config> Hash for Blob<C> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<C: Config> Write for Blob<C> {
     #[inline(always)]
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
@@ -267,7 +2839,7 @@ impl<C: Config> IntoIterator for Blob<C> {
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        self.data.into_iter()
+        self.into_vec().into_iter()
     }
 }
 
@@ -375,68 +2947,3 @@ impl<C: Config> BorrowMut<Vec<u8>> for Blob<C> {
         &mut self.data
     }
 }
-
-impl<C: Config> serde::Serialize for Blob<C> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let encoded = self.encode_base64();
-
-        serializer.serialize_str(encoded.as_str())
-    }
-}
-
-impl<'de, C: Config> serde::Deserialize<'de> for Blob<C> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct BlobVisitor<C: Config>(PhantomData<C>);
-
-        impl<'de, C: Config> serde::de::Visitor<'de> for BlobVisitor<C> {
-            type Value = Blob<C>;
-
-            fn expecting(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                f.write_str("base64 encoded string or byte sequence")
-            }
-
-            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                FromStr::from_str(value).map_err(E::custom)
-            }
-
-            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Blob::from_vec(value.to_owned()))
-            }
-
-            fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                Ok(Blob::from_vec(value))
-            }
-
-            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
-            where
-                V: serde::de::SeqAccess<'de>,
-            {
-                // Preallocate the bytes vec if possible, but remain conservative
-                let mut bytes = Vec::with_capacity(visitor.size_hint().unwrap_or(0).min(4096));
-
-                while let Some(byte) = visitor.next_element()? {
-                    bytes.push(byte);
-                }
-
-                Ok(Blob::from_vec(bytes))
-            }
-        }
-
-        deserializer.deserialize_any(BlobVisitor(PhantomData))
-    }
-}
@@ -0,0 +1,52 @@
+//! `defmt` formatting of blobs, for logging from embedded firmware
+
+use defmt::{Format, Formatter};
+
+#[cfg(feature = "heapless")]
+use super::HeaplessBlob;
+use super::{Blob, Config, FixedBlob};
+
+/// Number of leading bytes shown in hex
+const PREVIEW_LEN: usize = 16;
+
+/// Writes the length and a hex preview of the leading bytes, so that large blobs do not
+/// flood the log transport
+fn format_preview(name: &str, bytes: &[u8], f: Formatter) {
+    let preview = &bytes[..bytes.len().min(PREVIEW_LEN)];
+
+    defmt::write!(
+        f,
+        "{=str}({=usize} bytes: {=[u8]:02x}",
+        name,
+        bytes.len(),
+        preview
+    );
+
+    if bytes.len() > PREVIEW_LEN {
+        defmt::write!(f, "..");
+    }
+
+    defmt::write!(f, ")");
+}
+
+/// Formats as `Blob(5 bytes: [01, 02, 03, 04, 05])`, showing at most 16 bytes
+impl<C: Config> Format for Blob<C> {
+    fn format(&self, f: Formatter) {
+        format_preview("Blob", self, f);
+    }
+}
+
+/// Formats as a `Blob` does
+impl<C: Config, const N: usize> Format for FixedBlob<C, N> {
+    fn format(&self, f: Formatter) {
+        format_preview("FixedBlob", &self[..], f);
+    }
+}
+
+/// Formats as a `Blob` does
+#[cfg(feature = "heapless")]
+impl<C: Config, const N: usize> Format for HeaplessBlob<C, N> {
+    fn format(&self, f: Formatter) {
+        format_preview("HeaplessBlob", self, f);
+    }
+}
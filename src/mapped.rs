@@ -0,0 +1,123 @@
+//! Blobs over memory-mapped files
+
+use alloc_crate::string::String;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::io;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Standard};
+
+/// Read-only blob structure over a memory-mapped file
+///
+/// Pages of the file are only loaded as they are read, so very large files can be encoded to
+/// a writer or serialized without first reading them into memory. It encodes, displays and
+/// serializes exactly as a `Blob<C>`.
+pub struct MmapBlob<C: Config = Standard> {
+    map: Mmap,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> MmapBlob<C> {
+    /// Memory-map the file at `path`
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, while the
+    /// `MmapBlob` exists.
+    #[inline]
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<MmapBlob<C>> {
+        MmapBlob::map(&File::open(path)?)
+    }
+
+    /// Memory-map an open file
+    ///
+    /// # Safety
+    ///
+    /// As with [`open`](#method.open), the file must not be modified or truncated while the
+    /// `MmapBlob` exists.
+    pub unsafe fn map(file: &File) -> io::Result<MmapBlob<C>> {
+        Ok(MmapBlob {
+            map: Mmap::map(file)?,
+            _config: PhantomData,
+        })
+    }
+
+    /// Encode the `MmapBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.map, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.map, writer)
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.map[..])
+    }
+}
+
+impl<C: Config> fmt::Debug for MmapBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MmapBlob")
+            .field("len", &self.map.len())
+            .finish()
+    }
+}
+
+impl<C: Config> Display for MmapBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.map, f)
+    }
+}
+
+impl<C: Config> PartialEq<Blob<C>> for MmapBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.map[..] == other[..]
+    }
+}
+
+impl<C: Config> Deref for MmapBlob<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for MmapBlob<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.map
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for MmapBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.map, serializer)
+    }
+}
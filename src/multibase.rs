@@ -0,0 +1,155 @@
+//! Multibase self-describing encodings
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use base64::DecodeError;
+
+use super::whitespace;
+use super::{
+    Base32, Base32Hex, Base32HexNoPad, Base32NoPad, Base58, Config, Hex, HexUpper, Standard,
+    StandardNoPad, UrlSafe, UrlSafeNoPad,
+};
+
+/// Encodings supported by multibase, identified by a single-character prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Multibase {
+    /// Lowercase hexadecimal, prefix `f`
+    Base16,
+    /// Uppercase hexadecimal, prefix `F`
+    Base16Upper,
+    /// Lowercase RFC 4648 base-32 without padding, prefix `b`
+    Base32,
+    /// Uppercase RFC 4648 base-32 without padding, prefix `B`
+    Base32Upper,
+    /// Lowercase RFC 4648 base-32 with padding, prefix `c`
+    Base32Pad,
+    /// Uppercase RFC 4648 base-32 with padding, prefix `C`
+    Base32PadUpper,
+    /// Lowercase RFC 4648 base32hex without padding, prefix `v`
+    Base32Hex,
+    /// Uppercase RFC 4648 base32hex without padding, prefix `V`
+    Base32HexUpper,
+    /// Lowercase RFC 4648 base32hex with padding, prefix `t`
+    Base32HexPad,
+    /// Uppercase RFC 4648 base32hex with padding, prefix `T`
+    Base32HexPadUpper,
+    /// Base-58 with the Bitcoin alphabet, prefix `z`
+    Base58Btc,
+    /// Standard base-64 without padding, prefix `m`
+    Base64,
+    /// Standard base-64 with padding, prefix `M`
+    Base64Pad,
+    /// URL-safe base-64 without padding, prefix `u`
+    Base64Url,
+    /// URL-safe base-64 with padding, prefix `U`
+    Base64UrlPad,
+}
+
+impl Multibase {
+    /// Returns the multibase prefix character for this encoding
+    pub fn prefix(self) -> char {
+        match self {
+            Multibase::Base16 => 'f',
+            Multibase::Base16Upper => 'F',
+            Multibase::Base32 => 'b',
+            Multibase::Base32Upper => 'B',
+            Multibase::Base32Pad => 'c',
+            Multibase::Base32PadUpper => 'C',
+            Multibase::Base32Hex => 'v',
+            Multibase::Base32HexUpper => 'V',
+            Multibase::Base32HexPad => 't',
+            Multibase::Base32HexPadUpper => 'T',
+            Multibase::Base58Btc => 'z',
+            Multibase::Base64 => 'm',
+            Multibase::Base64Pad => 'M',
+            Multibase::Base64Url => 'u',
+            Multibase::Base64UrlPad => 'U',
+        }
+    }
+
+    /// Returns the encoding identified by the given multibase prefix character, if supported
+    pub fn from_prefix(prefix: char) -> Option<Multibase> {
+        Some(match prefix {
+            'f' => Multibase::Base16,
+            'F' => Multibase::Base16Upper,
+            'b' => Multibase::Base32,
+            'B' => Multibase::Base32Upper,
+            'c' => Multibase::Base32Pad,
+            'C' => Multibase::Base32PadUpper,
+            'v' => Multibase::Base32Hex,
+            'V' => Multibase::Base32HexUpper,
+            't' => Multibase::Base32HexPad,
+            'T' => Multibase::Base32HexPadUpper,
+            'z' => Multibase::Base58Btc,
+            'm' => Multibase::Base64,
+            'M' => Multibase::Base64Pad,
+            'u' => Multibase::Base64Url,
+            'U' => Multibase::Base64UrlPad,
+            _ => return None,
+        })
+    }
+
+    /// Appends the prefixed encoding of `input` to `output`
+    pub(crate) fn encode(self, input: &[u8], output: &mut String) {
+        output.push(self.prefix());
+
+        let start = output.len();
+
+        match self {
+            Multibase::Base16 => Hex::encode(input, output),
+            Multibase::Base16Upper => HexUpper::encode(input, output),
+            Multibase::Base32 | Multibase::Base32Upper => Base32NoPad::encode(input, output),
+            Multibase::Base32Pad | Multibase::Base32PadUpper => Base32::encode(input, output),
+            Multibase::Base32Hex | Multibase::Base32HexUpper => {
+                Base32HexNoPad::encode(input, output)
+            }
+            Multibase::Base32HexPad | Multibase::Base32HexPadUpper => {
+                Base32Hex::encode(input, output)
+            }
+            Multibase::Base58Btc => Base58::encode(input, output),
+            Multibase::Base64 => StandardNoPad::encode(input, output),
+            Multibase::Base64Pad => Standard::encode(input, output),
+            Multibase::Base64Url => UrlSafeNoPad::encode(input, output),
+            Multibase::Base64UrlPad => UrlSafe::encode(input, output),
+        }
+
+        match self {
+            Multibase::Base32
+            | Multibase::Base32Pad
+            | Multibase::Base32Hex
+            | Multibase::Base32HexPad => output[start..].make_ascii_lowercase(),
+            _ => {}
+        }
+    }
+
+    /// Decodes prefixed `input`, appending the bytes to `output` and returning the encoding used
+    pub(crate) fn decode(input: &[u8], output: &mut Vec<u8>) -> Result<Multibase, DecodeError> {
+        let (&prefix, data) = input.split_first().ok_or(DecodeError::InvalidLength(0))?;
+
+        let base =
+            Multibase::from_prefix(prefix as char).ok_or(DecodeError::InvalidByte(0, prefix))?;
+
+        let result = match base {
+            Multibase::Base16 | Multibase::Base16Upper => Hex::decode(data, output),
+            Multibase::Base32 | Multibase::Base32Upper => Base32NoPad::decode(data, output),
+            Multibase::Base32Pad | Multibase::Base32PadUpper => Base32::decode(data, output),
+            Multibase::Base32Hex | Multibase::Base32HexUpper => {
+                Base32HexNoPad::decode(data, output)
+            }
+            Multibase::Base32HexPad | Multibase::Base32HexPadUpper => {
+                Base32Hex::decode(data, output)
+            }
+            Multibase::Base58Btc => Base58::decode(data, output),
+            Multibase::Base64 => StandardNoPad::decode(data, output),
+            Multibase::Base64Pad => Standard::decode(data, output),
+            Multibase::Base64Url => UrlSafeNoPad::decode(data, output),
+            Multibase::Base64UrlPad => UrlSafe::decode(data, output),
+        };
+
+        // offsets are reported relative to the whole input, including the prefix
+        result
+            .map(|_| base)
+            .map_err(|err| whitespace::shift_offsets(err, 1))
+    }
+}
@@ -0,0 +1,52 @@
+//! Conversions between blobs and Node.js values, for native addons
+
+use napi::bindgen_prelude::{
+    sys, Buffer, FromNapiValue, Result, ToNapiValue, TypeName, Uint8ArraySlice, ValidateNapiValue,
+    ValueType,
+};
+
+use super::{Blob, Config};
+
+impl<C: Config> TypeName for Blob<C> {
+    #[inline]
+    fn type_name() -> &'static str {
+        "Buffer"
+    }
+
+    #[inline]
+    fn value_type() -> ValueType {
+        ValueType::Object
+    }
+}
+
+/// Accepts any `Uint8Array`, including a `Buffer`
+impl<C: Config> ValidateNapiValue for Blob<C> {
+    #[inline]
+    unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+        Uint8ArraySlice::validate(env, napi_val)
+    }
+}
+
+/// Copies the bytes of a `Buffer` or `Uint8Array`
+impl<C: Config> FromNapiValue for Blob<C> {
+    #[inline]
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Blob<C>> {
+        Uint8ArraySlice::from_napi_value(env, napi_val).map(|bytes| Blob::from(&bytes[..]))
+    }
+}
+
+/// Converts to a `Buffer`, handing over the `Vec<u8>` without copying
+impl<C: Config> ToNapiValue for Blob<C> {
+    #[inline]
+    unsafe fn to_napi_value(env: sys::napi_env, val: Blob<C>) -> Result<sys::napi_value> {
+        Buffer::to_napi_value(env, Buffer::from(val.into_vec()))
+    }
+}
+
+/// Converts to a `Buffer` holding a copy of the bytes
+impl<C: Config> ToNapiValue for &Blob<C> {
+    #[inline]
+    unsafe fn to_napi_value(env: sys::napi_env, val: &Blob<C>) -> Result<sys::napi_value> {
+        Buffer::to_napi_value(env, Buffer::from(&val[..]))
+    }
+}
@@ -0,0 +1,96 @@
+//! OpenAPI schema of serialized blobs
+
+use alloc_crate::borrow::Cow;
+use alloc_crate::vec::Vec;
+
+use utoipa::__dev::ComposeSchema;
+use utoipa::openapi::schema::{
+    ArrayBuilder, KnownFormat, ObjectBuilder, Schema, SchemaFormat, Type,
+};
+use utoipa::openapi::RefOr;
+use utoipa::ToSchema;
+
+use super::{
+    AsArray, Ascii85, Base32, Base32Hex, Base32HexNoPad, Base32NoPad, Base58, Bcrypt, Blob,
+    Bounded, Config, CowBlob, Crockford, CrockfordCheck, Crypt, Hex, HexOrBase64, HexUpper,
+    ImapMutf7, LenientBlob, Mime, SharedBlob, Standard, StandardNoPad, UrlSafe, UrlSafeNoPad,
+};
+
+/// Returns the schema of a `Blob<C>` serialized to JSON
+fn blob_schema<C: Config>() -> RefOr<Schema> {
+    if C::SERIALIZE_AS_SEQ {
+        let byte = ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .minimum(Some(0))
+            .maximum(Some(255));
+
+        return ArrayBuilder::new()
+            .items(byte)
+            .max_items(C::MAX_DECODED_LEN)
+            .into();
+    }
+
+    let schema = ObjectBuilder::new().schema_type(Type::String);
+
+    let schema = match C::CONTENT_ENCODING {
+        // OpenAPI describes standard base-64 with the `byte` format
+        Some("base64") => schema
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Byte)))
+            .content_encoding("base64"),
+        Some(encoding) => schema.content_encoding(encoding),
+        None => schema,
+    };
+
+    schema.into()
+}
+
+// `ToSchema` derives compose generic types through `ComposeSchema`, which also requires the
+// type arguments to implement `ToSchema`, so configs describe a `Blob` of themselves. Derived
+// component names append those of the type arguments, such as `Blob_UrlSafeNoPad`.
+macro_rules! impl_to_schema {
+    ($([$($params:tt)*] $ty:ty: $config:ty, $name:expr;)*) => {
+        $(
+            impl<$($params)*> ComposeSchema for $ty {
+                #[inline]
+                fn compose(_generics: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+                    blob_schema::<$config>()
+                }
+            }
+
+            impl<$($params)*> ToSchema for $ty {
+                #[inline]
+                fn name() -> Cow<'static, str> {
+                    $name.into()
+                }
+            }
+        )*
+    };
+}
+
+impl_to_schema! {
+    [C: Config] Blob<C>: C, "Blob";
+    ['a, C: Config] CowBlob<'a, C>: C, "Blob";
+    [C: Config] LenientBlob<C>: C, "Blob";
+    [C: Config] SharedBlob<C>: C, "Blob";
+    [] Standard: Standard, "Standard";
+    [] StandardNoPad: StandardNoPad, "StandardNoPad";
+    [] UrlSafe: UrlSafe, "UrlSafe";
+    [] UrlSafeNoPad: UrlSafeNoPad, "UrlSafeNoPad";
+    [] Crypt: Crypt, "Crypt";
+    [] ImapMutf7: ImapMutf7, "ImapMutf7";
+    [] Bcrypt: Bcrypt, "Bcrypt";
+    [] Mime: Mime, "Mime";
+    [] Hex: Hex, "Hex";
+    [] HexUpper: HexUpper, "HexUpper";
+    [] Base32: Base32, "Base32";
+    [] Base32NoPad: Base32NoPad, "Base32NoPad";
+    [] Base32Hex: Base32Hex, "Base32Hex";
+    [] Base32HexNoPad: Base32HexNoPad, "Base32HexNoPad";
+    [] Crockford: Crockford, "Crockford";
+    [] CrockfordCheck: CrockfordCheck, "CrockfordCheck";
+    [] Base58: Base58, "Base58";
+    [] Ascii85: Ascii85, "Ascii85";
+    [C: Config, const MAX: usize] Bounded<C, MAX>: Bounded<C, MAX>, format!("Bounded{}", MAX);
+    [C: Config] AsArray<C>: AsArray<C>, "AsArray";
+    [C: Config] HexOrBase64<C>: HexOrBase64<C>, "HexOrBase64";
+}
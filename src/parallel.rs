@@ -0,0 +1,76 @@
+//! Encoding and decoding large blobs across threads
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use rayon::prelude::*;
+
+use super::in_place::decode_chunk;
+use super::{Blob, Config, Error};
+
+/// Number of bytes or symbols handled by each task, rounded to whole blocks
+const CHUNK_LEN: usize = 1 << 16;
+
+/// Encodes `input` in pieces of whole blocks across the rayon thread pool
+pub(crate) fn encode<C: Config>(input: &[u8]) -> String {
+    let block_len = match C::BLOCK_LEN {
+        Some(block_len) if input.len() > CHUNK_LEN => block_len,
+        _ => {
+            let mut encoded = String::new();
+
+            C::encode(input, &mut encoded);
+
+            return encoded;
+        }
+    };
+
+    let pieces: Vec<String> = input
+        .par_chunks((CHUNK_LEN / block_len).max(1) * block_len)
+        .map(|chunk| {
+            let mut encoded = String::new();
+
+            C::encode(chunk, &mut encoded);
+
+            encoded
+        })
+        .collect();
+
+    pieces.concat()
+}
+
+/// Decodes `input` as `Blob::decode_base64` would, in pieces of whole blocks across the rayon
+/// thread pool, reporting the first error in the input if there are several
+pub(crate) fn decode<C: Config>(input: &[u8]) -> Result<Blob<C>, Error> {
+    let quantum = match C::ENCODED_BLOCK_LEN {
+        Some(quantum) if input.len() > CHUNK_LEN => quantum,
+        _ => return Blob::decode_base64(input),
+    };
+
+    // whitespace would shift the block boundaries of configs that skip it
+    if input.par_iter().any(u8::is_ascii_whitespace) {
+        return Blob::decode_base64(input);
+    }
+
+    Blob::<C>::check_max_len(input, 0)?;
+
+    let chunk_len = (CHUNK_LEN / quantum).max(1) * quantum;
+
+    let pieces: Vec<Result<Vec<u8>, Error>> = (0..input.len().div_ceil(chunk_len))
+        .into_par_iter()
+        .map(|i| {
+            let start = i * chunk_len;
+            let end = input.len().min(start + chunk_len);
+            let mut output = Vec::new();
+
+            decode_chunk::<C>(input, start..end, &mut output, C::decode).map(|_| output)
+        })
+        .collect();
+
+    let mut data = Vec::new();
+
+    for piece in pieces {
+        data.extend_from_slice(&piece?);
+    }
+
+    Ok(Blob::from_vec(data))
+}
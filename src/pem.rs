@@ -0,0 +1,100 @@
+//! PEM, the textual encoding of certificates and keys described in RFC 7468
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+
+use super::armor::{self, Frame, LINE_BYTES};
+use super::{Blob, Config, Error, Standard};
+
+/// Error returned when decoding PEM into a `Blob`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PemError {
+    /// The base-64 body was invalid
+    Decode(Error),
+    /// The `BEGIN` or `END` line was missing, or the labels did not match
+    Malformed,
+}
+
+impl Display for PemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PemError::Decode(ref err) => err.fmt(f),
+            PemError::Malformed => f.write_str("Malformed PEM"),
+        }
+    }
+}
+
+impl StdError for PemError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            PemError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for PemError {
+    #[inline]
+    fn from(err: Error) -> PemError {
+        PemError::Decode(err)
+    }
+}
+
+/// Appends the PEM form of `data` to `output`
+pub(crate) fn encode(label: &str, data: &[u8], output: &mut String) {
+    output.reserve(2 * label.len() + 30 + data.len().div_ceil(LINE_BYTES) * 65);
+
+    output.push_str("-----BEGIN ");
+    output.push_str(label);
+    output.push_str("-----\n");
+
+    for line in data.chunks(LINE_BYTES) {
+        Standard::encode(line, output);
+        output.push('\n');
+    }
+
+    output.push_str("-----END ");
+    output.push_str(label);
+    output.push_str("-----\n");
+}
+
+/// Decodes the label and body of a PEM frame, which has no checksum line
+fn decode_frame(input: &[u8], frame: &Frame) -> Result<(String, Blob<Standard>), PemError> {
+    if frame.checksum.is_some() {
+        return Err(PemError::Malformed);
+    }
+
+    let label = armor::decode_label(frame).ok_or(PemError::Malformed)?;
+
+    Ok((label, armor::decode_body(input, frame)?))
+}
+
+/// Decodes the first PEM section in `input`, returning its label and bytes
+pub(crate) fn decode(input: &[u8]) -> Result<(String, Blob<Standard>), PemError> {
+    match armor::frame(input) {
+        Some(Ok(frame)) => decode_frame(input, &frame),
+        _ => Err(PemError::Malformed),
+    }
+}
+
+/// Decodes every PEM section in `input`, in order
+pub(crate) fn decode_bundle(input: &[u8]) -> Result<Vec<(String, Blob<Standard>)>, PemError> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+
+    while let Some(frame) = armor::frame(&input[start..]) {
+        let frame = frame.map_err(|_| PemError::Malformed)?;
+
+        let section = decode_frame(&input[start..], &frame).map_err(|err| match err {
+            PemError::Decode(err) => PemError::Decode(err.within(start, input.len())),
+            err => err,
+        })?;
+
+        sections.push(section);
+        start += frame.end;
+    }
+
+    Ok(sections)
+}
@@ -0,0 +1,65 @@
+//! Conversions between blobs and Python objects, for extension modules
+
+use alloc_crate::string::ToString;
+use core::convert::Infallible;
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::types::{
+    PyAnyMethods, PyByteArray, PyByteArrayMethods, PyBytes, PyBytesMethods, PyString,
+    PyStringMethods, PyTypeMethods,
+};
+use pyo3::{Borrowed, Bound, FromPyObject, IntoPyObject, PyAny, PyErr, PyResult, Python};
+
+use super::{Blob, Config};
+
+/// Accepts `bytes` and `bytearray` as raw bytes, and `str` as encoded text
+///
+/// Strings are decoded as `Blob::decode_base64` would, raising `ValueError` if they are not
+/// valid for `C`. Any other type raises `TypeError`.
+impl<'a, 'py, C: Config> FromPyObject<'a, 'py> for Blob<C> {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Blob<C>> {
+        if let Ok(bytes) = obj.cast::<PyBytes>() {
+            return Ok(Blob::from(bytes.as_bytes()));
+        }
+
+        if let Ok(bytes) = obj.cast::<PyByteArray>() {
+            return Ok(Blob::from_vec(bytes.to_vec()));
+        }
+
+        if let Ok(text) = obj.cast::<PyString>() {
+            return Blob::decode_base64(text.to_str()?)
+                .map_err(|err| PyValueError::new_err(err.to_string()));
+        }
+
+        Err(PyTypeError::new_err(format!(
+            "expected bytes, bytearray or str, not {}",
+            obj.get_type().name()?
+        )))
+    }
+}
+
+/// Converts to `bytes`
+impl<'py, C: Config> IntoPyObject<'py> for Blob<C> {
+    type Target = PyBytes;
+    type Output = Bound<'py, PyBytes>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Bound<'py, PyBytes>, Infallible> {
+        Ok(PyBytes::new(py, &self))
+    }
+}
+
+/// Converts to `bytes`
+impl<'py, C: Config> IntoPyObject<'py> for &Blob<C> {
+    type Target = PyBytes;
+    type Output = Bound<'py, PyBytes>;
+    type Error = Infallible;
+
+    #[inline]
+    fn into_pyobject(self, py: Python<'py>) -> Result<Bound<'py, PyBytes>, Infallible> {
+        Ok(PyBytes::new(py, self))
+    }
+}
@@ -0,0 +1,18 @@
+//! Random blobs, for nonces, salts and test fixtures
+
+use rand::distributions::{Distribution, Standard as StandardDistribution};
+use rand::Rng;
+
+use super::{Config, FixedBlob};
+
+/// Fills all `N` bytes from the generator, so `rng.gen::<FixedBlob<C, N>>()` makes a nonce or key
+impl<C: Config, const N: usize> Distribution<FixedBlob<C, N>> for StandardDistribution {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FixedBlob<C, N> {
+        let mut data = [0; N];
+
+        rng.fill_bytes(&mut data);
+
+        FixedBlob::new(data)
+    }
+}
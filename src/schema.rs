@@ -0,0 +1,72 @@
+//! JSON Schema of serialized blobs
+
+use alloc_crate::borrow::{Cow, ToOwned};
+use core::any;
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use super::{Blob, Config, CowBlob, LenientBlob, SharedBlob};
+
+/// Returns the schema of a `Blob<C>` serialized to JSON
+fn blob_schema<C: Config>() -> Schema {
+    if C::SERIALIZE_AS_SEQ {
+        let mut schema = json_schema!({
+            "type": "array",
+            "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+        });
+
+        if let Some(max) = C::MAX_DECODED_LEN {
+            schema.insert("maxItems".to_owned(), max.into());
+        }
+
+        return schema;
+    }
+
+    match C::CONTENT_ENCODING {
+        // OpenAPI describes standard base-64 with the `byte` format
+        Some("base64") => json_schema!({
+            "type": "string",
+            "format": "byte",
+            "contentEncoding": "base64",
+        }),
+        Some(encoding) => json_schema!({
+            "type": "string",
+            "contentEncoding": encoding,
+        }),
+        None => json_schema!({ "type": "string" }),
+    }
+}
+
+macro_rules! impl_json_schema {
+    ($([$($params:tt)*] $ty:ty,)*) => {
+        $(
+            impl<$($params)* C: Config> JsonSchema for $ty {
+                #[inline]
+                fn inline_schema() -> bool {
+                    true
+                }
+
+                #[inline]
+                fn schema_name() -> Cow<'static, str> {
+                    "Blob".into()
+                }
+
+                fn schema_id() -> Cow<'static, str> {
+                    format!("blob::Blob<{}>", any::type_name::<C>()).into()
+                }
+
+                #[inline]
+                fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+                    blob_schema::<C>()
+                }
+            }
+        )*
+    };
+}
+
+impl_json_schema! {
+    [] Blob<C>,
+    ['a,] CowBlob<'a, C>,
+    [] LenientBlob<C>,
+    [] SharedBlob<C>,
+}
@@ -0,0 +1,212 @@
+//! Blobs encrypted at rest with XChaCha20-Poly1305
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+use chacha20poly1305::aead::rand_core::{CryptoRng, RngCore};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Error, Standard};
+
+/// Length of the random nonce at the start of a `SealedBlob`
+const NONCE_LEN: usize = 24;
+
+/// Length of the authentication tag at the end of a `SealedBlob`
+const TAG_LEN: usize = 16;
+
+/// Error returned when a `SealedBlob` could not be opened
+///
+/// Deliberately opaque, so a wrong key, a truncated blob and tampered bytes are
+/// indistinguishable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenError;
+
+impl Display for OpenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Failed to open sealed blob")
+    }
+}
+
+impl StdError for OpenError {}
+
+/// Blob structure holding bytes encrypted and authenticated with XChaCha20-Poly1305
+///
+/// The bytes are a random 24-byte nonce followed by the ciphertext and its tag, which encode,
+/// display and serialize as a single `Blob<C>` would. The nonce is long enough to be picked at
+/// random for every seal, so one 32-byte key can seal any number of blobs.
+pub struct SealedBlob<C: Config = Standard> {
+    data: Blob<C>,
+}
+
+impl<C: Config> SealedBlob<C> {
+    /// Encrypt the given `Blob` under `key`, with a nonce from the operating system
+    #[inline]
+    pub fn seal(key: &[u8; 32], blob: &Blob<C>) -> SealedBlob<C> {
+        SealedBlob::seal_with_rng(key, blob, &mut OsRng)
+    }
+
+    /// Encrypt the given `Blob` under `key`, with a nonce from `rng`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the blob is longer than XChaCha20-Poly1305 can encrypt, about 256 GiB.
+    pub fn seal_with_rng<R: CryptoRng + RngCore>(
+        key: &[u8; 32],
+        blob: &Blob<C>,
+        rng: &mut R,
+    ) -> SealedBlob<C> {
+        let nonce = XChaCha20Poly1305::generate_nonce(rng);
+
+        let ciphertext = XChaCha20Poly1305::new(key.into())
+            .encrypt(&nonce, &blob[..])
+            .expect("blob too long to seal");
+
+        let mut data = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+
+        SealedBlob::from(Blob::from(data))
+    }
+
+    /// Decrypt the bytes with `key`, failing if they were not sealed under it or were altered
+    pub fn open(&self, key: &[u8; 32]) -> Result<Blob<C>, OpenError> {
+        if self.data.len() < NONCE_LEN + TAG_LEN {
+            return Err(OpenError);
+        }
+
+        let (nonce, ciphertext) = self.data.split_at(NONCE_LEN);
+
+        XChaCha20Poly1305::new(key.into())
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map(Blob::from)
+            .map_err(|_| OpenError)
+    }
+
+    /// Decode a `SealedBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// The bytes are not checked until they are opened.
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<SealedBlob<C>, Error> {
+        Blob::decode_base64(encoded).map(SealedBlob::from)
+    }
+
+    /// Encode the nonce and ciphertext to a string, using base-64 or whichever encoding `C`
+    /// specifies
+    #[inline]
+    pub fn encode_base64(&self) -> String {
+        self.data.encode_base64()
+    }
+
+    /// Borrow the nonce and ciphertext
+    #[inline]
+    pub fn as_blob(&self) -> &Blob<C> {
+        &self.data
+    }
+
+    /// Consume self and return the nonce and ciphertext
+    #[inline]
+    pub fn into_blob(self) -> Blob<C> {
+        self.data
+    }
+}
+
+impl<C: Config> Clone for SealedBlob<C> {
+    #[inline]
+    fn clone(&self) -> SealedBlob<C> {
+        SealedBlob {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<C: Config> fmt::Debug for SealedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SealedBlob").field(&&self.data[..]).finish()
+    }
+}
+
+impl<C: Config> Display for SealedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `SealedBlob` from a string, as `Blob` would
+impl<C: Config> FromStr for SealedBlob<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(SealedBlob::from)
+    }
+}
+
+impl<C: Config> PartialEq for SealedBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config> Eq for SealedBlob<C> {}
+
+impl<C: Config> Hash for SealedBlob<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data[..].hash(state);
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for SealedBlob<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Wraps bytes previously returned by `into_blob`
+impl<C: Config> From<Blob<C>> for SealedBlob<C> {
+    #[inline(always)]
+    fn from(data: Blob<C>) -> SealedBlob<C> {
+        SealedBlob { data }
+    }
+}
+
+impl<C: Config> From<SealedBlob<C>> for Blob<C> {
+    #[inline(always)]
+    fn from(blob: SealedBlob<C>) -> Blob<C> {
+        blob.data
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for SealedBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for SealedBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(SealedBlob::from)
+    }
+}
@@ -0,0 +1,273 @@
+//! Blobs holding key material, wiped from memory when no longer needed
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::any;
+use core::fmt;
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+use core::mem;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+#[cfg(feature = "serde")]
+use super::{check_raw_len, whitespace, BlobVisitor, SeqByte};
+use super::{Blob, Config, Error, FixedBlob, Standard};
+
+/// Zeroes every allocated byte and clears the `Blob`
+impl<C: Config> Zeroize for Blob<C> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+/// Wipes the bytes when the `Blob` is dropped
+///
+/// Bytes moved out by `into_vec` or a conversion are no longer owned by the `Blob`, and are not
+/// wiped.
+impl<C: Config> Drop for Blob<C> {
+    #[inline]
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl<C: Config> ZeroizeOnDrop for Blob<C> {}
+
+impl<C: Config, const N: usize> Zeroize for FixedBlob<C, N> {
+    #[inline]
+    fn zeroize(&mut self) {
+        (**self).zeroize();
+    }
+}
+
+/// Blob structure for key material, which is wiped from memory when dropped
+///
+/// Unlike a plain `Blob`, `Debug` and `Display` never show the bytes, and encoding returns
+/// `Zeroizing` text, so keys are not left behind in logs or freed buffers. It deserializes as a
+/// `Blob<C>` would, wiping any buffers it decodes through, but does not implement `Serialize`,
+/// so secrets are only written out deliberately through `expose_secret`. `Base58` and
+/// `Crockford` still use working buffers of their own that are not wiped.
+pub struct SecretBlob<C: Config = Standard> {
+    blob: Blob<C>,
+}
+
+impl<C: Config> SecretBlob<C> {
+    /// Wrap the given `Blob`, taking ownership of its allocation
+    #[inline]
+    pub fn new(blob: Blob<C>) -> SecretBlob<C> {
+        SecretBlob { blob }
+    }
+
+    /// Decode a `SecretBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// The bytes are decoded into a buffer allocated at their greatest possible length up
+    /// front, so no partial copies are freed while decoding, and it is wiped if decoding fails.
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<SecretBlob<C>, Error> {
+        let encoded = encoded.as_ref();
+
+        Blob::<C>::check_max_len(encoded, 0)?;
+
+        let config = any::type_name::<C>();
+        let max_len = match C::decoded_len_bounds(encoded.len()) {
+            (_, usize::MAX) => encoded.len(),
+            (_, max_len) => max_len,
+        };
+
+        let mut data = Zeroizing::new(Vec::with_capacity(max_len));
+
+        C::decode(encoded, &mut data).map_err(|err| Error::new(err, config, encoded.len()))?;
+
+        Blob::<C>::check_canonical(encoded, &data, false)?;
+
+        Ok(SecretBlob::new(Blob::from_vec(mem::take(&mut *data))))
+    }
+
+    /// Encode the secret to a string, which is wiped when dropped
+    ///
+    /// The string is allocated at its final length up front, so no partial copies are freed
+    /// while encoding.
+    pub fn encode_base64(&self) -> Zeroizing<String> {
+        let mut encoded = Zeroizing::new(String::with_capacity(self.blob.encoded_len()));
+
+        C::encode(&self.blob, &mut encoded);
+
+        encoded
+    }
+
+    /// Returns the number of bytes of the secret
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.blob.len()
+    }
+
+    /// Returns `true` if the secret has no bytes
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.blob.is_empty()
+    }
+
+    /// Borrow the secret bytes
+    #[inline]
+    pub fn expose_secret(&self) -> &Blob<C> {
+        &self.blob
+    }
+
+    /// Mutably borrow the secret bytes
+    ///
+    /// Growing the `Blob` may reallocate it, leaving the old allocation unwiped.
+    #[inline]
+    pub fn expose_secret_mut(&mut self) -> &mut Blob<C> {
+        &mut self.blob
+    }
+}
+
+impl<C: Config> Clone for SecretBlob<C> {
+    #[inline]
+    fn clone(&self) -> SecretBlob<C> {
+        SecretBlob::new(self.blob.clone())
+    }
+}
+
+/// Shows only the length of the secret
+impl<C: Config> fmt::Debug for SecretBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretBlob([REDACTED; {}])", self.blob.len())
+    }
+}
+
+/// Never shows the secret
+impl<C: Config> fmt::Display for SecretBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+/// Compares in constant time, as `Blob::ct_eq` does
+impl<C: Config> PartialEq for SecretBlob<C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.blob.ct_eq(&other.blob)
+    }
+}
+
+impl<C: Config> Eq for SecretBlob<C> {}
+
+impl<C: Config> From<Blob<C>> for SecretBlob<C> {
+    #[inline(always)]
+    fn from(blob: Blob<C>) -> SecretBlob<C> {
+        SecretBlob::new(blob)
+    }
+}
+
+impl<C: Config> Zeroize for SecretBlob<C> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.blob.zeroize();
+    }
+}
+
+/// The inner `Blob` wipes its bytes when dropped
+impl<C: Config> ZeroizeOnDrop for SecretBlob<C> {}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for SecretBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = SecretVisitor {
+            _config: PhantomData,
+        };
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+/// Deserializes a `SecretBlob` as a `Blob` would be, wiping any intermediate copies of the bytes
+///
+/// The Node.js `Buffer` and Extended JSON forms are decoded as a `Blob` first.
+#[cfg(feature = "serde")]
+struct SecretVisitor<C: Config> {
+    _config: PhantomData<fn() -> C>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::de::Visitor<'de> for SecretVisitor<C> {
+    type Value = SecretBlob<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let (offset, trimmed) = whitespace::trim(value);
+
+        // offsets are reported relative to the untrimmed string
+        SecretBlob::decode_base64(trimmed).map_err(|err| E::custom(err.within(offset, value.len())))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(SecretBlob::new(Blob::from(value)))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let value = Zeroizing::new(value);
+
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(SecretBlob::new(Blob::from(&value[..])))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        let capacity = visitor.size_hint().unwrap_or(0).min(4096);
+        let mut data = Zeroizing::new(Vec::with_capacity(capacity));
+
+        while let Some(SeqByte(byte)) = visitor.next_element()? {
+            check_raw_len::<C, V::Error>(data.len() + 1)?;
+
+            // grow into a new buffer by hand, so that the old one is wiped
+            if data.len() == data.capacity() {
+                let mut grown = Zeroizing::new(Vec::with_capacity((data.len() * 2).max(32)));
+
+                grown.extend_from_slice(&data);
+                data = grown;
+            }
+
+            data.push(byte);
+        }
+
+        Ok(SecretBlob::new(Blob::from_vec(mem::take(&mut *data))))
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        BlobVisitor::<C>::new(false)
+            .visit_map(visitor)
+            .map(SecretBlob::new)
+    }
+}
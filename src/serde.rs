@@ -0,0 +1,555 @@
+//! Helper modules for using `Blob` encodings on plain byte fields
+//!
+//! Each module provides `serialize` and `deserialize` functions for use with
+//! `#[serde(with = "...")]` on `Vec<u8>`, `Box<[u8]>`, `Cow<[u8]>`, `&[u8]` and `[u8; N]`
+//! fields, which then (de)serialize exactly as a `Blob` of the corresponding config would.
+//! With the `heapless` feature, `heapless::Vec<u8, N>` fields are supported too.
+//!
+//! ```
+//! extern crate blob;
+//! #[macro_use]
+//! extern crate serde_derive;
+//! extern crate serde_json;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Message {
+//!     #[serde(with = "blob::serde::base64url_nopad")]
+//!     id: [u8; 4],
+//!     #[serde(with = "blob::serde::base64")]
+//!     body: Vec<u8>,
+//! }
+//!
+//! fn main() {
+//!     let json = r#"{"id":"3q2-7w","body":"AQIDBAU="}"#;
+//!     let message: Message = serde_json::from_str(json).unwrap();
+//!
+//!     assert_eq!(message.id, [0xde, 0xad, 0xbe, 0xef]);
+//!     assert_eq!(message.body, [1, 2, 3, 4, 5]);
+//!     assert_eq!(serde_json::to_string(&message).unwrap(), json);
+//! }
+//! ```
+//!
+//! Borrowed `&[u8]` fields can only be deserialized from raw bytes lent by the deserializer,
+//! as encoded strings must be decoded into an owned buffer.
+//!
+//! Each module also contains `option` and `vec` modules for `Option<Vec<u8>>` and
+//! `Vec<Vec<u8>>` fields, or those of any other byte container above. As with any `with`
+//! module, `Option` fields also need `#[serde(default)]` to allow the field to be missing.
+//!
+//! The [`extended_json`](extended_json/index.html) module emits MongoDB Extended JSON instead,
+//! and [`yaml_binary`](yaml_binary/index.html) tagged YAML binary scalars.
+//!
+//! With the `serde_with` feature, every built-in config, as well as `Blob<C>` for any config
+//! `C`, also implements `SerializeAs` and `DeserializeAs` for the same byte containers, so
+//! fields can be annotated with `#[serde_as(as = "blob::UrlSafeNoPad")]` instead.
+
+use alloc_crate::borrow::Cow;
+use alloc_crate::boxed::Box;
+use alloc_crate::vec::Vec;
+use core::convert::TryInto;
+use core::marker::PhantomData;
+
+use serde_crate::de::Error;
+use serde_crate::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde_with")]
+use serde_with::{DeserializeAs, SerializeAs};
+
+use super::{extjson, Blob, Config, CowBlob, Standard};
+#[cfg(feature = "serde_with")]
+use super::{
+    AsArray, Ascii85, Base32, Base32Hex, Base32HexNoPad, Base32NoPad, Base58, Bcrypt, Bounded,
+    Crockford, CrockfordCheck, Crypt, Hex, HexUpper, ImapMutf7, Mime, StandardNoPad, UrlSafe,
+    UrlSafeNoPad,
+};
+
+/// Byte containers that can be deserialized by the helper modules
+pub trait FromBytes<'de>: Sized {
+    /// Converts deserialized bytes, which may be borrowed from the input
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E>;
+}
+
+impl<'de> FromBytes<'de> for Vec<u8> {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        Ok(bytes.into_owned())
+    }
+}
+
+impl<'de> FromBytes<'de> for Box<[u8]> {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        Ok(bytes.into_owned().into_boxed_slice())
+    }
+}
+
+impl<'de> FromBytes<'de> for Cow<'de, [u8]> {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        Ok(bytes)
+    }
+}
+
+impl<'de> FromBytes<'de> for &'de [u8] {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        match bytes {
+            Cow::Borrowed(bytes) => Ok(bytes),
+            Cow::Owned(_) => Err(E::custom("cannot borrow decoded bytes")),
+        }
+    }
+}
+
+impl<'de, C: Config> FromBytes<'de> for Blob<C> {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        Ok(Blob::from_vec(bytes.into_owned()))
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'de, const N: usize> FromBytes<'de> for heapless::Vec<u8, N> {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        heapless::Vec::from_slice(&bytes)
+            .map_err(|_| E::invalid_length(bytes.len(), &format!("at most {} bytes", N).as_str()))
+    }
+}
+
+impl<'de, const N: usize> FromBytes<'de> for [u8; N] {
+    #[inline]
+    fn from_bytes<E: Error>(bytes: Cow<'de, [u8]>) -> Result<Self, E> {
+        let len = bytes.len();
+
+        bytes
+            .as_ref()
+            .try_into()
+            .map_err(|_| E::invalid_length(len, &format!("{} bytes", N).as_str()))
+    }
+}
+
+/// Serializes `bytes` as a `Blob<C>` would
+pub fn serialize_as<C, T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: Config,
+    T: AsRef<[u8]> + ?Sized,
+    S: Serializer,
+{
+    super::serialize_bytes::<C, S>(bytes.as_ref(), serializer)
+}
+
+/// Deserializes bytes as a `Blob<C>` would, borrowing them from the input when possible
+pub fn deserialize_as<'de, C, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    C: Config,
+    T: FromBytes<'de>,
+    D: Deserializer<'de>,
+{
+    let blob = CowBlob::<C>::deserialize(deserializer)?;
+
+    T::from_bytes(blob.into_cow())
+}
+
+/// Serializes an optional value as an optional `Blob<C>` would
+pub fn serialize_option_as<C, T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: Config,
+    T: AsRef<[u8]>,
+    S: Serializer,
+{
+    match *bytes {
+        Some(ref bytes) => serializer.serialize_some(&Encoded::<C, _>::new(bytes)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserializes an optional value as an optional `Blob<C>` would
+pub fn deserialize_option_as<'de, C, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    C: Config,
+    T: FromBytes<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<Decoded<C, T>>::deserialize(deserializer).map(|bytes| bytes.map(|bytes| bytes.bytes))
+}
+
+/// Serializes a sequence of values as a sequence of `Blob<C>` would
+pub fn serialize_seq_as<C, I, S>(items: I, serializer: S) -> Result<S::Ok, S::Error>
+where
+    C: Config,
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+    S: Serializer,
+{
+    serializer.collect_seq(items.into_iter().map(Encoded::<C, _>::new))
+}
+
+/// Deserializes a sequence of values as a sequence of `Blob<C>` would
+pub fn deserialize_seq_as<'de, C, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    C: Config,
+    T: FromBytes<'de>,
+    D: Deserializer<'de>,
+{
+    Vec::<Decoded<C, T>>::deserialize(deserializer)
+        .map(|items| items.into_iter().map(|item| item.bytes).collect())
+}
+
+/// Bytes that serialize as a `Blob<C>` would, for nesting within other types
+struct Encoded<C, T> {
+    bytes: T,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C, T> Encoded<C, T> {
+    #[inline]
+    fn new(bytes: T) -> Encoded<C, T> {
+        Encoded {
+            bytes,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config, T: AsRef<[u8]>> Serialize for Encoded<C, T> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_as::<C, T, S>(&self.bytes, serializer)
+    }
+}
+
+/// Bytes that deserialize as a `Blob<C>` would, for nesting within other types
+struct Decoded<C, T> {
+    bytes: T,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'de, C: Config, T: FromBytes<'de>> Deserialize<'de> for Decoded<C, T> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_as::<C, T, D>(deserializer).map(|bytes| Decoded {
+            bytes,
+            _config: PhantomData,
+        })
+    }
+}
+
+macro_rules! impl_serde_modules {
+    ($($(#[$($attrs:tt)*])* $module:ident: $config:ident,)*) => {
+        $(
+            $(#[$($attrs)*])*
+            pub mod $module {
+                use serde_crate::{Deserializer, Serializer};
+
+                use super::FromBytes;
+
+                /// Serializes the bytes as an encoded string, or raw bytes for binary formats
+                #[inline]
+                pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    T: AsRef<[u8]> + ?Sized,
+                    S: Serializer,
+                {
+                    super::serialize_as::<::$config, T, S>(bytes, serializer)
+                }
+
+                /// Deserializes an encoded string, or a raw byte sequence
+                #[inline]
+                pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+                where
+                    T: FromBytes<'de>,
+                    D: Deserializer<'de>,
+                {
+                    super::deserialize_as::<::$config, T, D>(deserializer)
+                }
+
+                /// Helpers for `Option` fields of bytes
+                pub mod option {
+                    use serde_crate::{Deserializer, Serializer};
+
+                    use super::super::FromBytes;
+
+                    /// Serializes the bytes if present, as an encoded string or raw bytes
+                    #[inline]
+                    pub fn serialize<T, S>(
+                        bytes: &Option<T>,
+                        serializer: S,
+                    ) -> Result<S::Ok, S::Error>
+                    where
+                        T: AsRef<[u8]>,
+                        S: Serializer,
+                    {
+                        super::super::serialize_option_as::<::$config, T, S>(bytes, serializer)
+                    }
+
+                    /// Deserializes an encoded string or raw byte sequence, if present
+                    #[inline]
+                    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+                    where
+                        T: FromBytes<'de>,
+                        D: Deserializer<'de>,
+                    {
+                        super::super::deserialize_option_as::<::$config, T, D>(deserializer)
+                    }
+                }
+
+                /// Helpers for sequences of bytes, such as `Vec<Vec<u8>>` fields
+                pub mod vec {
+                    use alloc_crate::vec::Vec;
+
+                    use serde_crate::{Deserializer, Serializer};
+
+                    use super::super::FromBytes;
+
+                    /// Serializes each item as an encoded string or raw bytes
+                    #[inline]
+                    pub fn serialize<I, S>(items: I, serializer: S) -> Result<S::Ok, S::Error>
+                    where
+                        I: IntoIterator,
+                        I::Item: AsRef<[u8]>,
+                        S: Serializer,
+                    {
+                        super::super::serialize_seq_as::<::$config, I, S>(items, serializer)
+                    }
+
+                    /// Deserializes a sequence of encoded strings or raw byte sequences
+                    #[inline]
+                    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+                    where
+                        T: FromBytes<'de>,
+                        D: Deserializer<'de>,
+                    {
+                        super::super::deserialize_seq_as::<::$config, T, D>(deserializer)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_modules! {
+    /// Standard base-64 with padding, as with [`Standard`](../enum.Standard.html)
+    base64: Standard,
+
+    /// Standard base-64 without padding, as with [`StandardNoPad`](../enum.StandardNoPad.html)
+    base64_nopad: StandardNoPad,
+
+    /// URL-safe base-64 with padding, as with [`UrlSafe`](../enum.UrlSafe.html)
+    base64url: UrlSafe,
+
+    /// URL-safe base-64 without padding, as with [`UrlSafeNoPad`](../enum.UrlSafeNoPad.html)
+    base64url_nopad: UrlSafeNoPad,
+
+    /// Lowercase hexadecimal, as with [`Hex`](../enum.Hex.html)
+    hex: Hex,
+
+    /// RFC 4648 base-32 with padding, as with [`Base32`](../enum.Base32.html)
+    base32: Base32,
+
+    /// Base-58 with the Bitcoin alphabet, as with [`Base58`](../enum.Base58.html)
+    base58: Base58,
+}
+
+/// MongoDB Extended JSON, as `{"$binary":{"base64":"...","subType":"00"}}`
+///
+/// Binary formats that are not human-readable store the raw bytes instead. Any form accepted
+/// by a `Blob` may be deserialized, including Extended JSON in canonical or legacy form.
+pub mod extended_json {
+    use serde_crate::{Deserializer, Serialize, Serializer};
+
+    use super::{extjson, FromBytes, Standard};
+
+    /// Serializes the bytes as a canonical Extended JSON binary value of the generic subtype
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            extjson::Binary(bytes.as_ref()).serialize(serializer)
+        } else {
+            serializer.serialize_bytes(bytes.as_ref())
+        }
+    }
+
+    /// Deserializes an Extended JSON binary value, or any other form accepted by a `Blob`
+    #[inline]
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromBytes<'de>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_as::<Standard, T, D>(deserializer)
+    }
+}
+
+/// YAML binary scalars, as produced for `!!binary` or `!binary` tagged values
+///
+/// Deserializing accepts the base-64 payload with any line breaks or indentation left by block
+/// scalars, both untagged and with the tag resolved by the deserializer, as `serde_yaml` does for
+/// `!!binary`. Local `!binary` tags, which serde presents as an enum variant, are accepted too.
+///
+/// Serde cannot emit `!!` shorthand tags, so serializing produces the local `!binary` tag used
+/// by Ruby's YAML library instead. Binary formats that are not human-readable store the raw
+/// bytes, as with the other helper modules.
+pub mod yaml_binary {
+    use alloc_crate::borrow::Cow;
+    use alloc_crate::string::String;
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use serde_crate::de::{self, EnumAccess, VariantAccess};
+    use serde_crate::{Deserializer, Serializer};
+
+    use super::{Blob, Config, FromBytes, Standard};
+
+    /// Serializes the bytes as a base-64 scalar with the local `!binary` tag
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(bytes.as_ref());
+        }
+
+        let mut encoded = String::new();
+
+        Standard::encode(bytes.as_ref(), &mut encoded);
+
+        serializer.serialize_newtype_variant("Binary", 0, "binary", &encoded)
+    }
+
+    /// Deserializes a base-64 scalar, which may span several lines or carry a binary tag
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromBytes<'de>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BinaryVisitor(PhantomData))
+        } else {
+            super::deserialize_as::<Standard, T, D>(deserializer)
+        }
+    }
+
+    struct BinaryVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: FromBytes<'de>> de::Visitor<'de> for BinaryVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("YAML binary scalar")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let blob = Blob::<Standard>::decode_base64_lenient(value).map_err(E::custom)?;
+
+            T::from_bytes(Cow::Owned(blob.into_vec()))
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: EnumAccess<'de>,
+        {
+            let (tag, value) = data.variant::<String>()?;
+
+            match tag.as_str() {
+                "binary" | "!binary" | "tag:yaml.org,2002:binary" => {
+                    self.visit_str(&value.newtype_variant::<String>()?)
+                }
+                _ => Err(de::Error::unknown_variant(&tag, &["binary"])),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_with")]
+macro_rules! impl_serde_as {
+    ($([$($params:tt)*] $config:ty,)*) => {
+        $(
+            impl<$($params)* T> SerializeAs<T> for $config
+            where
+                T: AsRef<[u8]> + ?Sized,
+            {
+                #[inline]
+                fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                {
+                    serialize_as::<$config, T, S>(source, serializer)
+                }
+            }
+
+            impl<'de, $($params)* T> DeserializeAs<'de, T> for $config
+            where
+                T: FromBytes<'de>,
+            {
+                #[inline]
+                fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    deserialize_as::<$config, T, D>(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde_with")]
+impl_serde_as! {
+    [] Standard,
+    [] StandardNoPad,
+    [] UrlSafe,
+    [] UrlSafeNoPad,
+    [] Crypt,
+    [] ImapMutf7,
+    [] Bcrypt,
+    [] Mime,
+    [] Hex,
+    [] HexUpper,
+    [] Base32,
+    [] Base32NoPad,
+    [] Base32Hex,
+    [] Base32HexNoPad,
+    [] Crockford,
+    [] CrockfordCheck,
+    [] Base58,
+    [] Ascii85,
+    [C: Config, const MAX: usize,] Bounded<C, MAX>,
+    [C: Config,] AsArray<C>,
+}
+
+/// Encodes as `C`, for configs without their own `SerializeAs` implementation
+#[cfg(feature = "serde_with")]
+impl<C: Config, T: AsRef<[u8]> + ?Sized> SerializeAs<T> for Blob<C> {
+    #[inline]
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_as::<C, T, S>(source, serializer)
+    }
+}
+
+/// Decodes as `C`, for configs without their own `DeserializeAs` implementation
+#[cfg(feature = "serde_with")]
+impl<'de, C: Config, T: FromBytes<'de>> DeserializeAs<'de, T> for Blob<C> {
+    #[inline]
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_as::<C, T, D>(deserializer)
+    }
+}
@@ -0,0 +1,533 @@
+//! Serde support for `Blob`, and the visitors shared by the other blob types
+
+use alloc_crate::borrow::ToOwned;
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::any;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+
+use serde_crate as serde;
+
+use super::{extjson, Blob, BlobRef, Config, Error, Standard};
+
+impl<C: Config> serde::Serialize for Blob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+/// Serializes `bytes` as a `Blob<C>`
+pub(crate) fn serialize_bytes<C: Config, S: serde::Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    // binary formats can store the bytes directly
+    if !serializer.is_human_readable() {
+        return serializer.serialize_bytes(bytes);
+    }
+
+    if C::SERIALIZE_AS_SEQ {
+        return serializer.collect_seq(bytes);
+    }
+
+    // formats that write to a stream never see the whole encoded string
+    serializer.collect_str(&BlobRef::<C>::new(bytes))
+}
+
+pub(crate) struct BlobVisitor<C: Config> {
+    lenient: bool,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> BlobVisitor<C> {
+    #[inline]
+    pub(crate) fn new(lenient: bool) -> BlobVisitor<C> {
+        BlobVisitor {
+            lenient,
+            _config: PhantomData,
+        }
+    }
+}
+
+/// Strips the surrounding noise accepted by `LenientBlob` from an encoded string
+fn trim_lenient(mut value: &str) -> &str {
+    value = value.trim_matches(|c: char| c.is_ascii_whitespace());
+
+    for quote in &['"', '\''] {
+        if value.len() >= 2 && value.starts_with(*quote) && value.ends_with(*quote) {
+            value = value[1..value.len() - 1].trim_matches(|c: char| c.is_ascii_whitespace());
+            break;
+        }
+    }
+
+    for prefix in &["base64:", "b64:"] {
+        if let Some(rest) = value.strip_prefix(prefix) {
+            value = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            break;
+        }
+    }
+
+    value
+}
+
+impl<'de, C: Config> serde::de::Visitor<'de> for BlobVisitor<C> {
+    type Value = Blob<C>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if self.lenient {
+            Blob::decode_base64_lenient(trim_lenient(value)).map_err(E::custom)
+        } else {
+            FromStr::from_str(value).map_err(E::custom)
+        }
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(Blob::from_vec(value.to_owned()))
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        Ok(Blob::from_vec(value))
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        let max = C::MAX_DECODED_LEN.unwrap_or(usize::MAX);
+
+        // Preallocate the bytes vec if possible, but remain conservative
+        let mut bytes = Vec::with_capacity(visitor.size_hint().unwrap_or(0).min(4096).min(max));
+
+        let seed = SeqByteSeed {
+            signed: self.lenient,
+        };
+
+        while let Some(SeqByte(byte)) = visitor.next_element_seed(seed)? {
+            check_raw_len::<C, V::Error>(bytes.len() + 1)?;
+
+            bytes.push(byte);
+        }
+
+        Ok(Blob::from_vec(bytes))
+    }
+
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        use serde_crate::de::{Error, Unexpected};
+
+        const FIELDS: &[&str] = &["type", "data", "$binary", "$type"];
+
+        // Node.js serializes a `Buffer` as `{"type":"Buffer","data":[...]}`, while MongoDB
+        // Extended JSON uses `{"$binary":{"base64":"...","subType":"00"}}`, or the legacy
+        // `{"$binary":"...","$type":"00"}`
+        let mut kind: Option<String> = None;
+        let mut data = None;
+        let mut extended = None;
+
+        while let Some(key) = visitor.next_key::<String>()? {
+            let is_extended = key.starts_with('$');
+
+            let field = match FIELDS.iter().find(|field| **field == key) {
+                Some(field) if *extended.get_or_insert(is_extended) == is_extended => *field,
+                _ => return Err(V::Error::unknown_field(&key, FIELDS)),
+            };
+
+            match field {
+                "type" | "$type" if kind.is_some() => return Err(V::Error::duplicate_field(field)),
+                "data" | "$binary" if data.is_some() => {
+                    return Err(V::Error::duplicate_field(field))
+                }
+                "type" | "$type" => kind = Some(visitor.next_value()?),
+                "data" => {
+                    data = Some(visitor.next_value_seed(SeqSeed(BlobVisitor::new(self.lenient)))?)
+                }
+                _ => data = Some(visitor.next_value_seed(extjson::BinarySeed::new())?),
+            }
+        }
+
+        let extended = extended.unwrap_or(false);
+
+        match kind {
+            Some(ref kind) if extended => extjson::check_subtype(kind)?,
+            None if extended => {}
+            Some(ref kind) if kind == "Buffer" => {}
+            Some(ref kind) => {
+                return Err(V::Error::invalid_value(Unexpected::Str(kind), &"Buffer"))
+            }
+            None => return Err(V::Error::missing_field("type")),
+        }
+
+        data.ok_or_else(|| V::Error::missing_field(if extended { "$binary" } else { "data" }))
+    }
+}
+
+/// Element of a byte sequence, from 0 to 255
+pub(crate) struct SeqByte(pub(crate) u8);
+
+impl<'de> serde::Deserialize<'de> for SeqByte {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_u8(SeqByteVisitor { signed: false })
+    }
+}
+
+/// Deserializes a `SeqByte`, which with `signed` may also be signed as produced by Java's
+/// `byte[]`
+///
+/// Negative values map to their two's complement, so `-1` becomes `255`.
+#[derive(Clone, Copy)]
+pub(crate) struct SeqByteSeed {
+    pub(crate) signed: bool,
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for SeqByteSeed {
+    type Value = SeqByte;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<SeqByte, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_u8(SeqByteVisitor {
+            signed: self.signed,
+        })
+    }
+}
+
+struct SeqByteVisitor {
+    signed: bool,
+}
+
+impl<'de> serde::de::Visitor<'de> for SeqByteVisitor {
+    type Value = SeqByte;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.signed {
+            f.write_str("a byte from -128 to 255")
+        } else {
+            f.write_str("a byte from 0 to 255")
+        }
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if value <= u8::MAX as u64 {
+            Ok(SeqByte(value as u8))
+        } else {
+            Err(E::invalid_value(
+                serde::de::Unexpected::Unsigned(value),
+                &self,
+            ))
+        }
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let min = if self.signed { i8::MIN as i64 } else { 0 };
+
+        if value >= min && value <= u8::MAX as i64 {
+            Ok(SeqByte(value as u8))
+        } else {
+            Err(E::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &self,
+            ))
+        }
+    }
+}
+
+/// Deserializes a `Blob` from a sequence of bytes only, such as the data of a Node.js `Buffer`
+struct SeqSeed<C: Config>(BlobVisitor<C>);
+
+impl<'de, C: Config> serde::de::DeserializeSeed<'de> for SeqSeed<C> {
+    type Value = Blob<C>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self.0)
+    }
+}
+
+/// Blob types holding up to `N` bytes inline, deserialized with an `InlineVisitor`
+pub(crate) trait InlineBlob<C: Config, const N: usize>:
+    FromStr<Err = Error> + Sized
+{
+    /// Whether bytes beyond `N` spill to the heap, rather than being rejected
+    const SPILL: bool;
+
+    /// Copies bytes into a new blob
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Adopts or copies owned bytes into a new blob
+    fn from_byte_vec(bytes: Vec<u8>) -> Result<Self, Error>;
+}
+
+/// Deserializes an `InlineBlob` as a `Blob<C>` would be, without allocating for bytes that fit
+/// inline
+pub(crate) struct InlineVisitor<C, T, const N: usize> {
+    _blob: PhantomData<fn() -> (C, T)>,
+}
+
+impl<C: Config, T: InlineBlob<C, N>, const N: usize> InlineVisitor<C, T, N> {
+    #[inline]
+    pub(crate) fn new() -> InlineVisitor<C, T, N> {
+        InlineVisitor { _blob: PhantomData }
+    }
+}
+
+impl<'de, C: Config, T: InlineBlob<C, N>, const N: usize> serde::de::Visitor<'de>
+    for InlineVisitor<C, T, N>
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("base64 encoded string or byte sequence")
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        T::from_str(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        T::from_bytes(value).map_err(E::custom)
+    }
+
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<T, E>
+    where
+        E: serde::de::Error,
+    {
+        check_raw_len::<C, E>(value.len())?;
+
+        T::from_byte_vec(value).map_err(E::custom)
+    }
+
+    fn visit_seq<V>(self, mut visitor: V) -> Result<T, V::Error>
+    where
+        V: serde::de::SeqAccess<'de>,
+    {
+        use serde_crate::de::Error as _;
+
+        let mut buf = [0; N];
+        let mut len = 0;
+
+        // fill the inline buffer first, spilling to the heap only once it is full
+        while let Some(SeqByte(byte)) = visitor.next_element()? {
+            check_raw_len::<C, V::Error>(len + 1)?;
+
+            if len < N {
+                buf[len] = byte;
+                len += 1;
+                continue;
+            }
+
+            if !T::SPILL {
+                let err = Error::too_long(N, any::type_name::<C>(), N + 1);
+
+                return Err(V::Error::custom(err));
+            }
+
+            let mut bytes = Vec::with_capacity(N * 2);
+
+            bytes.extend_from_slice(&buf);
+            bytes.push(byte);
+
+            while let Some(SeqByte(byte)) = visitor.next_element()? {
+                check_raw_len::<C, V::Error>(bytes.len() + 1)?;
+
+                bytes.push(byte);
+            }
+
+            return T::from_byte_vec(bytes).map_err(V::Error::custom);
+        }
+
+        T::from_bytes(&buf[..len]).map_err(V::Error::custom)
+    }
+
+    fn visit_map<V>(self, visitor: V) -> Result<T, V::Error>
+    where
+        V: serde::de::MapAccess<'de>,
+    {
+        let blob = BlobVisitor::<C>::new(false).visit_map(visitor)?;
+
+        T::from_byte_vec(blob.into_vec()).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserializes an `InlineBlob` as a `Blob<C>` would be
+pub(crate) fn deserialize_inline<'de, C, T, D, const N: usize>(
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    C: Config,
+    T: InlineBlob<C, N>,
+    D: serde::Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(InlineVisitor::<C, T, N>::new())
+    } else {
+        deserializer.deserialize_bytes(InlineVisitor::<C, T, N>::new())
+    }
+}
+
+/// Checks the length of raw bytes being deserialized against `C::MAX_DECODED_LEN`, if any
+#[inline]
+pub(crate) fn check_raw_len<C: Config, E: serde::de::Error>(len: usize) -> Result<(), E> {
+    match C::MAX_DECODED_LEN {
+        Some(max) if len > max => Err(E::custom(Error::too_long(max, any::type_name::<C>(), len))),
+        _ => Ok(()),
+    }
+}
+
+impl<'de, C: Config> serde::Deserialize<'de> for Blob<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(BlobVisitor::new(false))
+        } else {
+            deserializer.deserialize_bytes(BlobVisitor::new(false))
+        }
+    }
+}
+
+/// Wrapper around `Blob` with a more forgiving `Deserialize` implementation
+///
+/// When deserializing from a string, the following are applied, in order, before decoding:
+///
+/// 1. Leading and trailing ASCII whitespace is trimmed.
+/// 2. A single pair of matching surrounding `"` or `'` quotes is removed, along with
+///    any ASCII whitespace just inside them.
+/// 3. A leading `base64:` or `b64:` scheme prefix is removed, along with any ASCII
+///    whitespace directly following it.
+/// 4. The remainder is decoded with
+///    [`Blob::decode_base64_lenient`](struct.Blob.html#method.decode_base64_lenient),
+///    skipping any ASCII whitespace within it.
+///
+/// Byte sequences are accepted as with `Blob`, except that their elements may also be signed,
+/// from -128 to -1, as produced for Java's `byte[]`. Negative values map to their two's
+/// complement, so `-1` becomes `255`. Serialization is identical to `Blob`.
+pub struct LenientBlob<C: Config = Standard>(pub Blob<C>);
+
+impl<C: Config> LenientBlob<C> {
+    /// Consume self and return the inner `Blob`
+    #[inline]
+    pub fn into_inner(self) -> Blob<C> {
+        self.0
+    }
+}
+
+impl<C: Config> Clone for LenientBlob<C> {
+    #[inline]
+    fn clone(&self) -> LenientBlob<C> {
+        LenientBlob(self.0.clone())
+    }
+}
+
+impl<C: Config> fmt::Debug for LenientBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LenientBlob").field(&self.0.data).finish()
+    }
+}
+
+impl<C: Config> PartialEq for LenientBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: Config> Eq for LenientBlob<C> {}
+
+impl<C: Config> From<Blob<C>> for LenientBlob<C> {
+    #[inline(always)]
+    fn from(blob: Blob<C>) -> LenientBlob<C> {
+        LenientBlob(blob)
+    }
+}
+
+impl<C: Config> Deref for LenientBlob<C> {
+    type Target = Blob<C>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Blob<C> {
+        &self.0
+    }
+}
+
+impl<C: Config> DerefMut for LenientBlob<C> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Blob<C> {
+        &mut self.0
+    }
+}
+
+impl<C: Config> serde::Serialize for LenientBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, C: Config> serde::Deserialize<'de> for LenientBlob<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let visitor = BlobVisitor::new(true);
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor).map(LenientBlob)
+        } else {
+            deserializer.deserialize_bytes(visitor).map(LenientBlob)
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! Reference-counted blobs, for cheap clones
+
+use alloc_crate::string::String;
+use alloc_crate::sync::Arc;
+use alloc_crate::vec::Vec;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{Blob, Config, Error, Standard};
+
+/// Blob structure sharing its binary data through an `Arc<[u8]>`
+///
+/// Cloning a `SharedBlob` only increments a reference count, so one decoded payload can be
+/// handed to many threads or tasks. It encodes, displays and serializes exactly as a `Blob<C>`.
+pub struct SharedBlob<C: Config = Standard> {
+    data: Arc<[u8]>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> SharedBlob<C> {
+    /// Create a `SharedBlob` from already shared bytes
+    #[inline]
+    pub fn from_arc(data: Arc<[u8]>) -> SharedBlob<C> {
+        SharedBlob {
+            data,
+            _config: PhantomData,
+        }
+    }
+
+    /// Decode a `SharedBlob` from the given string, as `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<SharedBlob<C>, Error> {
+        Blob::decode_base64(encoded).map(SharedBlob::from)
+    }
+
+    /// Encode the `SharedBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self.data[..])
+    }
+
+    /// Consume self and return the inner `Arc<[u8]>`
+    #[inline]
+    pub fn into_arc(self) -> Arc<[u8]> {
+        self.data
+    }
+}
+
+/// Clones the reference to the bytes, not the bytes themselves
+impl<C: Config> Clone for SharedBlob<C> {
+    #[inline]
+    fn clone(&self) -> SharedBlob<C> {
+        SharedBlob::from_arc(self.data.clone())
+    }
+}
+
+impl<C: Config> Default for SharedBlob<C> {
+    #[inline]
+    fn default() -> SharedBlob<C> {
+        SharedBlob::from_arc(Arc::from(&[][..]))
+    }
+}
+
+impl<C: Config> fmt::Debug for SharedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SharedBlob").field(&&*self.data).finish()
+    }
+}
+
+impl<C: Config> Display for SharedBlob<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `SharedBlob` from a string, as `Blob` would
+impl<C: Config> FromStr for SharedBlob<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(SharedBlob::from)
+    }
+}
+
+impl<C: Config> PartialEq for SharedBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config> Eq for SharedBlob<C> {}
+
+impl<C: Config> PartialEq<Blob<C>> for SharedBlob<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        *self.data == other[..]
+    }
+}
+
+impl<C: Config> Hash for SharedBlob<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl<C: Config> Deref for SharedBlob<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for SharedBlob<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> From<Arc<[u8]>> for SharedBlob<C> {
+    #[inline(always)]
+    fn from(data: Arc<[u8]>) -> SharedBlob<C> {
+        SharedBlob::from_arc(data)
+    }
+}
+
+impl<C: Config> From<Blob<C>> for SharedBlob<C> {
+    #[inline]
+    fn from(blob: Blob<C>) -> SharedBlob<C> {
+        SharedBlob::from_arc(blob.into_vec().into())
+    }
+}
+
+impl<'a, C: Config> From<&'a [u8]> for SharedBlob<C> {
+    #[inline]
+    fn from(data: &'a [u8]) -> SharedBlob<C> {
+        SharedBlob::from_arc(data.into())
+    }
+}
+
+impl<C: Config> From<SharedBlob<C>> for Blob<C> {
+    #[inline]
+    fn from(blob: SharedBlob<C>) -> Blob<C> {
+        blob.to_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for SharedBlob<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for SharedBlob<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(SharedBlob::from)
+    }
+}
+
+/// Blob structure sharing its binary data until it is mutated, through an `Arc<Vec<u8>>`
+///
+/// Cloning a `CowShared` only increments a reference count. Mutation, including through
+/// `DerefMut`, first copies the bytes if they are still shared with any clone, so a cache can
+/// hand out cheap clones of large blobs that are rarely written to. It encodes, displays and
+/// serializes exactly as a `Blob<C>`.
+pub struct CowShared<C: Config = Standard> {
+    data: Arc<Vec<u8>>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> CowShared<C> {
+    /// Decode a `CowShared` from the given string, as `Blob::decode_base64` would
+    #[inline]
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<CowShared<C>, Error> {
+        Blob::decode_base64(encoded).map(CowShared::from)
+    }
+
+    /// Encode the `CowShared` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(&self.data, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(&self.data, writer)
+    }
+
+    /// Returns `true` if no clone shares the bytes, so mutating them will not copy
+    #[inline]
+    pub fn is_unique(&self) -> bool {
+        Arc::strong_count(&self.data) == 1 && Arc::weak_count(&self.data) == 0
+    }
+
+    /// Returns the bytes for mutation, first copying them if they are shared
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut Vec<u8> {
+        Arc::make_mut(&mut self.data)
+    }
+
+    /// Convert into an owned `Blob`, copying the bytes only if they are shared
+    #[inline]
+    pub fn into_blob(self) -> Blob<C> {
+        Blob::from_vec(Arc::try_unwrap(self.data).unwrap_or_else(|data| (*data).clone()))
+    }
+}
+
+/// Clones the reference to the bytes, not the bytes themselves
+impl<C: Config> Clone for CowShared<C> {
+    #[inline]
+    fn clone(&self) -> CowShared<C> {
+        CowShared {
+            data: self.data.clone(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> Default for CowShared<C> {
+    #[inline]
+    fn default() -> CowShared<C> {
+        CowShared::from(Vec::new())
+    }
+}
+
+impl<C: Config> fmt::Debug for CowShared<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CowShared").field(&&self.data[..]).finish()
+    }
+}
+
+impl<C: Config> Display for CowShared<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)
+    }
+}
+
+/// Decodes a `CowShared` from a string, as `Blob` would
+impl<C: Config> FromStr for CowShared<C> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Blob::from_str(s).map(CowShared::from)
+    }
+}
+
+impl<C: Config> PartialEq for CowShared<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<C: Config> Eq for CowShared<C> {}
+
+impl<C: Config> PartialEq<Blob<C>> for CowShared<C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.data[..] == other[..]
+    }
+}
+
+impl<C: Config> Hash for CowShared<C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+    }
+}
+
+impl<C: Config> Deref for CowShared<C> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Copies the bytes first if they are shared, as with `make_mut`
+impl<C: Config> DerefMut for CowShared<C> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.make_mut()
+    }
+}
+
+impl<C: Config> AsRef<[u8]> for CowShared<C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<C: Config> Extend<u8> for CowShared<C> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        self.make_mut().extend(iter);
+    }
+}
+
+impl<C: Config> From<Vec<u8>> for CowShared<C> {
+    #[inline]
+    fn from(data: Vec<u8>) -> CowShared<C> {
+        CowShared {
+            data: Arc::new(data),
+            _config: PhantomData,
+        }
+    }
+}
+
+/// Takes ownership of the `Vec<u8>` without copying
+impl<C: Config> From<Blob<C>> for CowShared<C> {
+    #[inline]
+    fn from(blob: Blob<C>) -> CowShared<C> {
+        CowShared::from(blob.into_vec())
+    }
+}
+
+impl<C: Config> From<CowShared<C>> for Blob<C> {
+    #[inline]
+    fn from(blob: CowShared<C>) -> Blob<C> {
+        blob.into_blob()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config> serde::Serialize for CowShared<C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(&self.data, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config> serde::Deserialize<'de> for CowShared<C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Blob::deserialize(deserializer).map(CowShared::from)
+    }
+}
@@ -0,0 +1,254 @@
+//! Blobs carrying a MAC over their bytes, for signed tokens and webhook payloads
+
+use alloc_crate::string::String;
+use core::error::Error as StdError;
+use core::fmt::{self, Display};
+use core::marker::PhantomData;
+
+use digest::{KeyInit, Mac};
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+use super::{Blob, Config, Error, Standard};
+
+/// Error returned when verifying a `SignedBlob`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The encoded data or tag was invalid
+    Decode(Error),
+    /// The encoded form had no `.` between the data and the tag
+    MissingTag,
+    /// The key was not a valid length for the MAC
+    InvalidKey,
+    /// The tag did not match the data
+    Mismatch,
+}
+
+impl Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SignatureError::Decode(ref err) => err.fmt(f),
+            SignatureError::MissingTag => f.write_str("Missing signature"),
+            SignatureError::InvalidKey => f.write_str("Invalid key length"),
+            SignatureError::Mismatch => f.write_str("Signature mismatch"),
+        }
+    }
+}
+
+impl StdError for SignatureError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            SignatureError::Decode(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for SignatureError {
+    #[inline]
+    fn from(err: Error) -> SignatureError {
+        SignatureError::Decode(err)
+    }
+}
+
+/// Blob structure paired with its MAC `M`, such as `hmac::Hmac<sha2::Sha256>`
+///
+/// It encodes as `base64(data) + "." + base64(tag)`, using `C` for both halves, so `C` must not
+/// use `.` as a symbol. Only `verify` and `verifying` construct one from text, and both check
+/// the tag in constant time, so a `SignedBlob` always holds a tag that matched its data.
+///
+/// It serializes as that string, but can only be deserialized through the `DeserializeSeed`
+/// returned by `verifying`, since a plain `Deserialize` has no way to receive the key.
+pub struct SignedBlob<M, C: Config = Standard> {
+    data: Blob<C>,
+    tag: Blob<C>,
+    _mac: PhantomData<fn() -> M>,
+}
+
+impl<M: Mac + KeyInit, C: Config> SignedBlob<M, C> {
+    /// Sign the given `Blob` with `key`
+    pub fn sign(data: Blob<C>, key: &[u8]) -> Result<SignedBlob<M, C>, SignatureError> {
+        let tag = data
+            .hmac::<M>(key)
+            .map_err(|_| SignatureError::InvalidKey)?;
+
+        Ok(SignedBlob {
+            tag: Blob::from(&tag[..]),
+            data,
+            _mac: PhantomData,
+        })
+    }
+
+    /// Decode a `SignedBlob` from `base64(data) + "." + base64(tag)`, checking the tag with `key`
+    ///
+    /// Both halves are decoded as strictly as `Blob::decode_base64` would.
+    pub fn verify<T: AsRef<[u8]>>(
+        encoded: T,
+        key: &[u8],
+    ) -> Result<SignedBlob<M, C>, SignatureError> {
+        let encoded = encoded.as_ref();
+
+        let dot = encoded
+            .iter()
+            .rposition(|&c| c == b'.')
+            .ok_or(SignatureError::MissingTag)?;
+
+        let data = Blob::<C>::decode_base64(&encoded[..dot])?;
+        let tag = Blob::<C>::decode_base64(&encoded[dot + 1..])?;
+
+        let mut mac =
+            <M as KeyInit>::new_from_slice(key).map_err(|_| SignatureError::InvalidKey)?;
+
+        Mac::update(&mut mac, &data);
+
+        mac.verify_slice(&tag)
+            .map_err(|_| SignatureError::Mismatch)?;
+
+        Ok(SignedBlob {
+            data,
+            tag,
+            _mac: PhantomData,
+        })
+    }
+
+    /// Returns a `DeserializeSeed` which deserializes a `SignedBlob` from its string form,
+    /// checking the tag with `key`
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn verifying(key: &[u8]) -> VerifyingSeed<'_, M, C> {
+        VerifyingSeed {
+            key,
+            _blob: PhantomData,
+        }
+    }
+}
+
+impl<M, C: Config> SignedBlob<M, C> {
+    /// Borrow the signed bytes
+    #[inline]
+    pub fn data(&self) -> &Blob<C> {
+        &self.data
+    }
+
+    /// Borrow the tag
+    #[inline]
+    pub fn tag(&self) -> &Blob<C> {
+        &self.tag
+    }
+
+    /// Consume self and return the signed bytes, dropping the tag
+    #[inline]
+    pub fn into_blob(self) -> Blob<C> {
+        self.data
+    }
+
+    /// Encode to `base64(data) + "." + base64(tag)`, using whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded =
+            String::with_capacity(self.data.encoded_len() + 1 + self.tag.encoded_len());
+
+        C::encode(&self.data, &mut encoded);
+        encoded.push('.');
+        C::encode(&self.tag, &mut encoded);
+
+        encoded
+    }
+}
+
+impl<M, C: Config> Clone for SignedBlob<M, C> {
+    #[inline]
+    fn clone(&self) -> SignedBlob<M, C> {
+        SignedBlob {
+            data: self.data.clone(),
+            tag: self.tag.clone(),
+            _mac: PhantomData,
+        }
+    }
+}
+
+impl<M, C: Config> fmt::Debug for SignedBlob<M, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignedBlob")
+            .field("data", &self.data)
+            .field("tag", &self.tag)
+            .finish()
+    }
+}
+
+impl<M, C: Config> Display for SignedBlob<M, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(&self.data, f)?;
+        f.write_str(".")?;
+        C::fmt(&self.tag, f)
+    }
+}
+
+/// Compares the tags in constant time, along with the data
+impl<M, C: Config> PartialEq for SignedBlob<M, C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.tag.ct_eq(&other.tag) && self.data == other.data
+    }
+}
+
+impl<M, C: Config> Eq for SignedBlob<M, C> {}
+
+#[cfg(feature = "serde")]
+impl<M, C: Config> serde::Serialize for SignedBlob<M, C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a `SignedBlob` from its string form, as returned by `SignedBlob::verifying`
+///
+/// Deserialization fails unless the tag matches the data under the key.
+#[cfg(feature = "serde")]
+pub struct VerifyingSeed<'a, M, C: Config = Standard> {
+    key: &'a [u8],
+    _blob: PhantomData<fn() -> SignedBlob<M, C>>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, M, C: Config> Clone for VerifyingSeed<'a, M, C> {
+    #[inline]
+    fn clone(&self) -> VerifyingSeed<'a, M, C> {
+        VerifyingSeed {
+            key: self.key,
+            _blob: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a, M: Mac + KeyInit, C: Config> serde::de::DeserializeSeed<'de>
+    for VerifyingSeed<'a, M, C>
+{
+    type Value = SignedBlob<M, C>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a, M: Mac + KeyInit, C: Config> serde::de::Visitor<'de> for VerifyingSeed<'a, M, C> {
+    type Value = SignedBlob<M, C>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a signed blob string")
+    }
+
+    #[inline]
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        SignedBlob::verify(v, self.key).map_err(E::custom)
+    }
+}
@@ -0,0 +1,58 @@
+//! SIMD-accelerated base-64 for configs with the standard or URL-safe alphabet
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+
+use base64::engine::Config as _;
+use base64::Engine as _;
+use base64_simd::{Base64, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+use super::__private::SimdAlphabet;
+use super::Base64Config;
+
+/// Returns the vectorized engine for the alphabet of `T` and the given padding, if any
+#[inline]
+fn engine<T: Base64Config>(pad: bool) -> Option<&'static Base64> {
+    match (T::SIMD_ALPHABET?, pad) {
+        (SimdAlphabet::Standard, true) => Some(&STANDARD),
+        (SimdAlphabet::Standard, false) => Some(&STANDARD_NO_PAD),
+        (SimdAlphabet::UrlSafe, true) => Some(&URL_SAFE),
+        (SimdAlphabet::UrlSafe, false) => Some(&URL_SAFE_NO_PAD),
+    }
+}
+
+/// Appends the encoded form of `input` to `output`, returning `false` if `T` is not supported
+#[inline]
+pub fn encode<T: Base64Config>(input: &[u8], output: &mut String) -> bool {
+    match engine::<T>(T::ENGINE.config().encode_padding()) {
+        Some(engine) => {
+            engine.encode_append(input, output);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Decodes `input`, appending the decoded bytes to `output`, returning `false` if `T` is not
+/// supported or the input is invalid
+///
+/// The vectorized decoders are strict, so any input they accept also decodes identically with
+/// the scalar engine, which is left to report errors.
+#[inline]
+pub fn decode<T: Base64Config>(input: &[u8], output: &mut Vec<u8>) -> bool {
+    // the scalar engines accept input with or without padding
+    let engine = match engine::<T>(input.ends_with(b"=")) {
+        Some(engine) => engine,
+        None => return false,
+    };
+
+    let len = output.len();
+
+    if engine.decode_append(input, output).is_err() {
+        output.truncate(len);
+
+        return false;
+    }
+
+    true
+}
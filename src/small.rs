@@ -0,0 +1,346 @@
+//! Blobs storing short binary data inline
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serde_impl::{deserialize_inline, InlineBlob};
+#[cfg(feature = "serde")]
+use super::serialize_bytes;
+use super::{whitespace, Blob, Config, Error};
+
+enum Storage<const N: usize> {
+    Inline { len: usize, buf: [u8; N] },
+    Heap(Vec<u8>),
+}
+
+/// Blob structure storing up to `N` bytes inline, spilling to the heap beyond that
+///
+/// Short values such as tokens, keys and hashes are held without a heap allocation, and
+/// base-64 or hex text that fits is decoded straight into the inline buffer. It encodes,
+/// displays and serializes exactly as a `Blob<C>`.
+pub struct SmallBlob<C: Config, const N: usize> {
+    data: Storage<N>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config, const N: usize> SmallBlob<C, N> {
+    /// Create a new empty `SmallBlob`
+    #[inline]
+    pub fn new() -> SmallBlob<C, N> {
+        SmallBlob {
+            data: Storage::Inline {
+                len: 0,
+                buf: [0; N],
+            },
+            _config: PhantomData,
+        }
+    }
+
+    /// Create a `SmallBlob` holding a copy of the given bytes
+    pub fn from_slice(bytes: &[u8]) -> SmallBlob<C, N> {
+        let mut blob = SmallBlob::new();
+
+        blob.extend_from_slice(bytes);
+
+        blob
+    }
+
+    /// Decode a `SmallBlob` from the given string, as `Blob::decode_base64` would
+    ///
+    /// Base-64 and hex configs decode inline whenever the bytes fit, while other configs, and
+    /// longer data, decode on the heap.
+    pub fn decode_base64<T: AsRef<[u8]>>(encoded: T) -> Result<SmallBlob<C, N>, Error> {
+        let encoded = encoded.as_ref();
+        let mut buf = [0; N];
+
+        if C::decoded_len_bounds(encoded.len()).0 <= N {
+            if let Some(result) = Blob::<C>::decode_to_slice(encoded, &mut buf) {
+                return result.map(|len| SmallBlob {
+                    data: Storage::Inline { len, buf },
+                    _config: PhantomData,
+                });
+            }
+        }
+
+        Blob::decode_base64(encoded).map(SmallBlob::from)
+    }
+
+    /// Encode the `SmallBlob` to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(self, &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(self, writer)
+    }
+
+    /// Returns `true` if the bytes are stored inline rather than on the heap
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        match self.data {
+            Storage::Inline { .. } => true,
+            Storage::Heap(_) => false,
+        }
+    }
+
+    /// Appends a byte, spilling to the heap if the inline capacity is exceeded
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends the given bytes, spilling to the heap if the inline capacity is exceeded
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        match self.data {
+            Storage::Inline {
+                ref mut len,
+                ref mut buf,
+            } if *len + bytes.len() <= N => {
+                buf[*len..*len + bytes.len()].copy_from_slice(bytes);
+                *len += bytes.len();
+            }
+            Storage::Inline { len, ref buf } => {
+                let mut vec = Vec::with_capacity(len + bytes.len());
+
+                vec.extend_from_slice(&buf[..len]);
+                vec.extend_from_slice(bytes);
+
+                self.data = Storage::Heap(vec);
+            }
+            Storage::Heap(ref mut vec) => vec.extend_from_slice(bytes),
+        }
+    }
+
+    /// Removes all bytes, keeping any heap allocation
+    #[inline]
+    pub fn clear(&mut self) {
+        match self.data {
+            Storage::Inline { ref mut len, .. } => *len = 0,
+            Storage::Heap(ref mut vec) => vec.clear(),
+        }
+    }
+
+    /// Copy the bytes into an owned `Blob`
+    #[inline]
+    pub fn to_blob(&self) -> Blob<C> {
+        Blob::from(&self[..])
+    }
+
+    /// Consume self and return the bytes as a `Vec<u8>`, reusing the heap allocation if any
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.data {
+            Storage::Inline { len, buf } => buf[..len].to_vec(),
+            Storage::Heap(vec) => vec,
+        }
+    }
+}
+
+impl<C: Config, const N: usize> Default for SmallBlob<C, N> {
+    #[inline]
+    fn default() -> SmallBlob<C, N> {
+        SmallBlob::new()
+    }
+}
+
+impl<C: Config, const N: usize> Clone for SmallBlob<C, N> {
+    #[inline]
+    fn clone(&self) -> SmallBlob<C, N> {
+        SmallBlob::from_slice(self)
+    }
+}
+
+impl<C: Config, const N: usize> fmt::Debug for SmallBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SmallBlob").field(&&self[..]).finish()
+    }
+}
+
+impl<C: Config, const N: usize> Display for SmallBlob<C, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(self, f)
+    }
+}
+
+/// Decodes a `SmallBlob` from a string, as `Blob` would
+impl<C: Config, const N: usize> FromStr for SmallBlob<C, N> {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, trimmed) = whitespace::trim(s);
+
+        // offsets are reported relative to the untrimmed string
+        SmallBlob::decode_base64(trimmed).map_err(|err| err.within(offset, s.len()))
+    }
+}
+
+impl<C: Config, const N: usize> PartialEq for SmallBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<C: Config, const N: usize> Eq for SmallBlob<C, N> {}
+
+impl<C: Config, const N: usize> PartialEq<Blob<C>> for SmallBlob<C, N> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self[..] == other[..]
+    }
+}
+
+impl<C: Config, const N: usize> Hash for SmallBlob<C, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state);
+    }
+}
+
+impl<C: Config, const N: usize> Deref for SmallBlob<C, N> {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        match self.data {
+            Storage::Inline { len, ref buf } => &buf[..len],
+            Storage::Heap(ref vec) => vec,
+        }
+    }
+}
+
+impl<C: Config, const N: usize> DerefMut for SmallBlob<C, N> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self.data {
+            Storage::Inline { len, ref mut buf } => &mut buf[..len],
+            Storage::Heap(ref mut vec) => vec,
+        }
+    }
+}
+
+impl<C: Config, const N: usize> AsRef<[u8]> for SmallBlob<C, N> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<C: Config, const N: usize> AsMut<[u8]> for SmallBlob<C, N> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl<C: Config, const N: usize> Extend<u8> for SmallBlob<C, N> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        for byte in iter {
+            self.push(byte);
+        }
+    }
+}
+
+impl<C: Config, const N: usize> FromIterator<u8> for SmallBlob<C, N> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> SmallBlob<C, N> {
+        let mut blob = SmallBlob::new();
+
+        blob.extend(iter);
+
+        blob
+    }
+}
+
+impl<'a, C: Config, const N: usize> From<&'a [u8]> for SmallBlob<C, N> {
+    #[inline]
+    fn from(bytes: &'a [u8]) -> SmallBlob<C, N> {
+        SmallBlob::from_slice(bytes)
+    }
+}
+
+/// Moves the bytes inline if they fit, otherwise keeps the heap allocation
+impl<C: Config, const N: usize> From<Vec<u8>> for SmallBlob<C, N> {
+    #[inline]
+    fn from(vec: Vec<u8>) -> SmallBlob<C, N> {
+        if vec.len() <= N {
+            SmallBlob::from_slice(&vec)
+        } else {
+            SmallBlob {
+                data: Storage::Heap(vec),
+                _config: PhantomData,
+            }
+        }
+    }
+}
+
+impl<C: Config, const N: usize> From<Blob<C>> for SmallBlob<C, N> {
+    #[inline]
+    fn from(blob: Blob<C>) -> SmallBlob<C, N> {
+        SmallBlob::from(blob.into_vec())
+    }
+}
+
+impl<C: Config, const N: usize> From<SmallBlob<C, N>> for Blob<C> {
+    #[inline]
+    fn from(blob: SmallBlob<C, N>) -> Blob<C> {
+        Blob::from_vec(blob.into_vec())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, const N: usize> serde::Serialize for SmallBlob<C, N> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_bytes::<C, S>(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: Config, const N: usize> serde::Deserialize<'de> for SmallBlob<C, N> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_inline::<C, SmallBlob<C, N>, D, N>(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Config, const N: usize> InlineBlob<C, N> for SmallBlob<C, N> {
+    const SPILL: bool = true;
+
+    #[inline]
+    fn from_bytes(bytes: &[u8]) -> Result<SmallBlob<C, N>, Error> {
+        Ok(SmallBlob::from_slice(bytes))
+    }
+
+    #[inline]
+    fn from_byte_vec(bytes: Vec<u8>) -> Result<SmallBlob<C, N>, Error> {
+        Ok(SmallBlob::from(bytes))
+    }
+}
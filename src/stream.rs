@@ -0,0 +1,425 @@
+//! Streaming I/O over blobs
+
+use alloc_crate::string::String;
+use alloc_crate::vec::Vec;
+use std::any;
+use std::fmt;
+use std::io::{self, BufRead, Cursor, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+
+use super::whitespace::remap_offsets;
+use super::{Blob, Config, Error, Standard};
+use base64::DecodeError;
+
+/// Number of bytes read from a reader at a time
+pub(crate) const READ_LEN: usize = 8 * 1024;
+
+/// Wraps a decoding error as an `io::Error` of kind `InvalidData`
+#[inline]
+pub(crate) fn invalid_data(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Decodes `C`-encoded text incrementally from `reader`, skipping ASCII whitespace
+pub(crate) fn decode_from_reader<C: Config, R: Read>(reader: R) -> io::Result<Blob<C>> {
+    let mut decoder = StreamDecoder::<C>::new();
+
+    read_into(reader, &mut decoder, |_| ())?;
+
+    decoder.finish().map_err(invalid_data)
+}
+
+/// Writes all of the text from `reader` into `decoder`, calling `f` after each write
+pub(crate) fn read_into<C, R, F>(
+    mut reader: R,
+    decoder: &mut StreamDecoder<C>,
+    mut f: F,
+) -> io::Result<()>
+where
+    C: Config,
+    R: Read,
+    F: FnMut(&mut StreamDecoder<C>),
+{
+    let mut buf = [0u8; READ_LEN];
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+
+        decoder.write(&buf[..n]).map_err(invalid_data)?;
+
+        f(decoder);
+    }
+}
+
+/// Pending symbols of a stream of encoded text, and the bytes decoded so far
+///
+/// Configs without an `ENCODED_BLOCK_LEN` buffer all of the text and decode it when finished.
+pub(crate) struct StreamDecoder<C: Config> {
+    blob: Blob<C>,
+    taken: usize,
+    symbols: Vec<u8>,
+    offsets: Vec<usize>,
+    input_len: usize,
+}
+
+impl<C: Config> StreamDecoder<C> {
+    pub(crate) fn new() -> StreamDecoder<C> {
+        StreamDecoder {
+            blob: Blob::new(),
+            taken: 0,
+            symbols: Vec::new(),
+            offsets: Vec::new(),
+            input_len: 0,
+        }
+    }
+
+    /// Buffers `input`, decoding every whole block before the last symbol
+    pub(crate) fn write(&mut self, input: &[u8]) -> Result<(), Error> {
+        let quantum = match C::ENCODED_BLOCK_LEN {
+            Some(quantum) => quantum,
+            None => {
+                self.symbols.extend_from_slice(input);
+                self.input_len += input.len();
+
+                return Ok(());
+            }
+        };
+
+        self.push(input);
+
+        // keep at least one symbol back, so that only the final chunk is decoded as such
+        let ready = self.symbols.len().saturating_sub(1) / quantum * quantum;
+
+        self.decode(ready, false)
+    }
+
+    /// Passes the bytes decoded so far to `f` and removes them, counting them towards
+    /// `C::MAX_DECODED_LEN` still
+    #[cfg(feature = "allocator-api2")]
+    pub(crate) fn take_decoded<F: FnOnce(&[u8])>(&mut self, f: F) {
+        f(&self.blob);
+
+        self.taken += self.blob.len();
+        self.blob.data.clear();
+    }
+
+    /// Decodes the remaining symbols as the end of the text, returning the bytes not yet taken
+    pub(crate) fn finish(mut self) -> Result<Blob<C>, Error> {
+        if C::ENCODED_BLOCK_LEN.is_none() {
+            return Blob::decode_base64_lenient(&self.symbols);
+        }
+
+        let len = self.symbols.len();
+
+        self.decode(len, true)?;
+
+        Ok(self.blob)
+    }
+
+    /// Buffers the symbols of `input`, skipping whitespace
+    fn push(&mut self, input: &[u8]) {
+        for (i, &c) in input.iter().enumerate() {
+            if !c.is_ascii_whitespace() {
+                self.symbols.push(c);
+                self.offsets.push(self.input_len + i);
+            }
+        }
+
+        self.input_len += input.len();
+    }
+
+    /// Decodes the first `len` pending symbols, which must be whole blocks unless `last`
+    fn decode(&mut self, len: usize, last: bool) -> Result<(), Error> {
+        if len == 0 && !last {
+            return Ok(());
+        }
+
+        let config = any::type_name::<C>();
+        let chunk = &self.symbols[..len];
+        let offsets = &self.offsets[..len];
+
+        // padding is only valid in the final chunk
+        if !last {
+            if let Some(j) = chunk.iter().position(|&c| c == b'=') {
+                let err = DecodeError::InvalidByte(offsets[j], b'=');
+
+                return Err(Error::new(err, config, self.input_len));
+            }
+        }
+
+        let start = self.blob.data.len();
+
+        C::decode(chunk, &mut self.blob.data)
+            .map_err(|err| Error::new(remap_offsets(err, offsets), config, self.input_len))?;
+
+        if let Some(max) = C::MAX_DECODED_LEN {
+            if self.taken + self.blob.data.len() > max {
+                return Err(Error::too_long(max, config, self.input_len));
+            }
+        }
+
+        Blob::<C>::check_canonical(chunk, &self.blob.data[start..], true).map_err(|err| {
+            let offset = err.offset().map(|j| offsets[j]);
+
+            Error::non_canonical(offset, err.byte(), config, self.input_len)
+        })?;
+
+        self.symbols.drain(..len);
+        self.offsets.drain(..len);
+
+        Ok(())
+    }
+}
+
+/// Writer decoding base-64 (or `C`-encoded) text written to it into a `Blob`
+///
+/// Text may be written in chunks of any size, split anywhere, and ASCII whitespace is ignored
+/// as with `Blob::decode_base64_lenient`. Whole blocks are decoded as they arrive, so invalid
+/// symbols fail the write that completes their block with `io::ErrorKind::InvalidData`, while
+/// padding and the final block are only checked by `finish`.
+pub struct DecodingWriter<C: Config = Standard> {
+    decoder: StreamDecoder<C>,
+}
+
+impl<C: Config> DecodingWriter<C> {
+    /// Create a new writer, which has not decoded any bytes yet
+    #[inline]
+    pub fn new() -> DecodingWriter<C> {
+        DecodingWriter {
+            decoder: StreamDecoder::new(),
+        }
+    }
+
+    /// Decodes any remaining text as the end of the input, returning the decoded `Blob`
+    #[inline]
+    pub fn finish(self) -> Result<Blob<C>, Error> {
+        self.decoder.finish()
+    }
+}
+
+impl<C: Config> Default for DecodingWriter<C> {
+    #[inline]
+    fn default() -> DecodingWriter<C> {
+        DecodingWriter::new()
+    }
+}
+
+impl<C: Config> fmt::Debug for DecodingWriter<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecodingWriter")
+            .field("decoded_len", &self.decoder.blob.len())
+            .field("input_len", &self.decoder.input_len)
+            .finish()
+    }
+}
+
+impl<C: Config> Write for DecodingWriter<C> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.decoder.write(buf).map_err(invalid_data)?;
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reader over the bytes of a `Blob`, as returned by [`Blob::reader`]
+///
+/// This reads, seeks and buffers like an `io::Cursor`, while the `Blob` it borrows stays
+/// available for use afterwards.
+///
+/// [`Blob::reader`]: struct.Blob.html#method.reader
+pub struct BlobReader<'a, C: Config = Standard> {
+    cursor: Cursor<&'a [u8]>,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'a, C: Config> BlobReader<'a, C> {
+    /// Create a reader over the given `Blob`, starting at the first byte
+    #[inline]
+    pub fn new(blob: &'a Blob<C>) -> BlobReader<'a, C> {
+        BlobReader {
+            cursor: Cursor::new(&blob[..]),
+            _config: PhantomData,
+        }
+    }
+
+    /// Returns the position of the next byte read
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    /// Sets the position of the next byte read, which may be past the end
+    #[inline]
+    pub fn set_position(&mut self, pos: u64) {
+        self.cursor.set_position(pos)
+    }
+
+    /// Returns the bytes that have not been read yet
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        let bytes = *self.cursor.get_ref();
+
+        &bytes[(self.cursor.position() as usize).min(bytes.len())..]
+    }
+}
+
+impl<'a, C: Config> Clone for BlobReader<'a, C> {
+    #[inline]
+    fn clone(&self) -> BlobReader<'a, C> {
+        BlobReader {
+            cursor: self.cursor.clone(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> fmt::Debug for BlobReader<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BlobReader")
+            .field("position", &self.cursor.position())
+            .field("len", &self.cursor.get_ref().len())
+            .finish()
+    }
+}
+
+impl<'a, C: Config> Read for BlobReader<'a, C> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.cursor.read(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.cursor.read_exact(buf)
+    }
+}
+
+impl<'a, C: Config> BufRead for BlobReader<'a, C> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.cursor.fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.cursor.consume(amt)
+    }
+}
+
+impl<'a, C: Config> Seek for BlobReader<'a, C> {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.cursor.seek(pos)
+    }
+}
+
+/// Reader over the encoded form of a `Blob`, as returned by [`Blob::encode_reader`]
+///
+/// Configs with a [`BLOCK_LEN`](trait.Config.html#associatedconstant.BLOCK_LEN) are encoded a
+/// few kilobytes at a time as the text is read, while others are encoded in full by the first
+/// read. The text read is exactly that of `Blob::encode_base64`.
+///
+/// [`Blob::encode_reader`]: struct.Blob.html#method.encode_reader
+pub struct EncodeReader<'a, C: Config = Standard> {
+    input: &'a [u8],
+    encoded: String,
+    pos: usize,
+    done: bool,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<'a, C: Config> EncodeReader<'a, C> {
+    /// Create a reader over the encoded form of the given `Blob`
+    #[inline]
+    pub fn new(blob: &'a Blob<C>) -> EncodeReader<'a, C> {
+        EncodeReader {
+            input: blob,
+            encoded: String::new(),
+            pos: 0,
+            done: false,
+            _config: PhantomData,
+        }
+    }
+
+    /// Encodes the next piece of the input once all previously encoded text has been read
+    fn refill(&mut self) {
+        while self.pos == self.encoded.len() && !self.done {
+            let len = match C::BLOCK_LEN {
+                Some(block_len) => block_len * (READ_LEN / block_len).max(1),
+                None => self.input.len(),
+            };
+
+            let (chunk, rest) = self.input.split_at(len.min(self.input.len()));
+
+            self.input = rest;
+            self.done = rest.is_empty();
+            self.encoded.clear();
+            self.pos = 0;
+
+            C::encode(chunk, &mut self.encoded);
+        }
+    }
+}
+
+impl<'a, C: Config> Clone for EncodeReader<'a, C> {
+    #[inline]
+    fn clone(&self) -> EncodeReader<'a, C> {
+        EncodeReader {
+            input: self.input,
+            encoded: self.encoded.clone(),
+            pos: self.pos,
+            done: self.done,
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<'a, C: Config> fmt::Debug for EncodeReader<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EncodeReader")
+            .field("pending", &&self.encoded[self.pos..])
+            .field("remaining_input", &self.input.len())
+            .finish()
+    }
+}
+
+impl<'a, C: Config> Read for EncodeReader<'a, C> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let pending = self.fill_buf()?;
+            let n = pending.len().min(buf.len());
+
+            buf[..n].copy_from_slice(&pending[..n]);
+            n
+        };
+
+        self.consume(n);
+
+        Ok(n)
+    }
+}
+
+impl<'a, C: Config> BufRead for EncodeReader<'a, C> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.refill();
+
+        Ok(&self.encoded.as_bytes()[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.encoded.len());
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions between blobs and JavaScript typed arrays and `Blob`s
+
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use js_sys::{Array, ArrayBuffer, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use super::{Blob, Config};
+
+/// Copies the bytes of the JavaScript array into a new `Blob`
+pub(crate) fn from_uint8array<C: Config>(array: &Uint8Array) -> Blob<C> {
+    Blob::from_vec(array.to_vec())
+}
+
+/// Copies the bytes into a new JavaScript `Uint8Array`
+pub(crate) fn to_uint8array(bytes: &[u8]) -> Uint8Array {
+    Uint8Array::from(bytes)
+}
+
+/// Copies the bytes into a new JavaScript `Blob`
+pub(crate) fn to_js_blob(bytes: &[u8]) -> Result<web_sys::Blob, JsValue> {
+    let parts = Array::of1(&to_uint8array(bytes));
+
+    web_sys::Blob::new_with_u8_array_sequence(&parts)
+}
+
+/// Future reading the bytes of a JavaScript `Blob`, as returned by `Blob::from_js_blob`
+pub(crate) struct FromJsBlob<C: Config> {
+    buffer: JsFuture,
+    _config: PhantomData<fn() -> C>,
+}
+
+impl<C: Config> FromJsBlob<C> {
+    #[inline]
+    pub(crate) fn new(blob: &web_sys::Blob) -> FromJsBlob<C> {
+        FromJsBlob {
+            buffer: JsFuture::from(blob.array_buffer()),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<C: Config> Future for FromJsBlob<C> {
+    type Output = Result<Blob<C>, JsValue>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Blob<C>, JsValue>> {
+        let this = self.get_mut();
+
+        Pin::new(&mut this.buffer).poll(cx).map(|buffer| {
+            let buffer: ArrayBuffer = buffer?.dyn_into()?;
+
+            Ok(from_uint8array(&Uint8Array::new(&buffer)))
+        })
+    }
+}
+
+impl<C: Config> From<Blob<C>> for Uint8Array {
+    #[inline]
+    fn from(blob: Blob<C>) -> Uint8Array {
+        to_uint8array(&blob)
+    }
+}
+
+impl<'a, C: Config> From<&'a Blob<C>> for Uint8Array {
+    #[inline]
+    fn from(blob: &'a Blob<C>) -> Uint8Array {
+        to_uint8array(blob)
+    }
+}
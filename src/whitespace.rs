@@ -0,0 +1,131 @@
+//! Decoding of encoded data interspersed with whitespace, such as line breaks
+
+use alloc_crate::vec::Vec;
+
+use base64::DecodeError;
+
+/// Size of the stack buffer of non-whitespace characters, a multiple of the
+/// base-64, base-32 and hex quanta
+const BUFFER_LEN: usize = 256;
+
+/// Maps the offsets of `err` through `offsets`, for errors in a cleaned copy of the input
+#[inline]
+pub fn remap_offsets(err: DecodeError, offsets: &[usize]) -> DecodeError {
+    match err {
+        DecodeError::InvalidByte(j, c) => DecodeError::InvalidByte(offsets[j], c),
+        DecodeError::InvalidLastSymbol(j, c) => DecodeError::InvalidLastSymbol(offsets[j], c),
+        DecodeError::InvalidLength(len) => DecodeError::InvalidLength(len),
+        DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+    }
+}
+
+/// Trims leading and trailing ASCII whitespace, returning the offset of the trimmed string
+pub fn trim(input: &str) -> (usize, &str) {
+    let trimmed = input.trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+    (
+        input.len() - trimmed.len(),
+        trimmed.trim_end_matches(|c: char| c.is_ascii_whitespace()),
+    )
+}
+
+/// Shifts the offsets of `err` by `offset`, for errors in a suffix of the original input
+pub fn shift_offsets(err: DecodeError, offset: usize) -> DecodeError {
+    match err {
+        DecodeError::InvalidByte(i, c) => DecodeError::InvalidByte(i + offset, c),
+        DecodeError::InvalidLastSymbol(i, c) => DecodeError::InvalidLastSymbol(i + offset, c),
+        DecodeError::InvalidLength(len) => DecodeError::InvalidLength(len),
+        DecodeError::InvalidPadding => DecodeError::InvalidPadding,
+    }
+}
+
+/// Decodes `input` with `decode`, skipping ASCII whitespace
+///
+/// The input is decoded in chunks through a small stack buffer instead of building a
+/// cleaned copy, so it must be valid for the encoding to decode chunks of 256 characters
+/// independently, where only the final chunk may contain `=` padding.
+///
+/// Error offsets are reported relative to the original `input`.
+pub fn decode<F>(input: &[u8], output: &mut Vec<u8>, decode: F) -> Result<(), DecodeError>
+where
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+{
+    decode_chunks(input, |chunk| decode(chunk, output))
+}
+
+/// Passes the non-whitespace characters of `input` to `f` in chunks of up to 256, remapping
+/// the offsets of any error
+fn decode_chunks<F>(input: &[u8], mut f: F) -> Result<(), DecodeError>
+where
+    F: FnMut(&[u8]) -> Result<(), DecodeError>,
+{
+    let mut buffer = [0u8; BUFFER_LEN];
+    let mut offsets = [0usize; BUFFER_LEN];
+    let mut len = 0;
+
+    let mut chars = input
+        .iter()
+        .enumerate()
+        .filter(|&(_, c)| !c.is_ascii_whitespace())
+        .peekable();
+
+    while let Some((i, &c)) = chars.next() {
+        buffer[len] = c;
+        offsets[len] = i;
+        len += 1;
+
+        if len == BUFFER_LEN || chars.peek().is_none() {
+            // padding is only valid in the final chunk
+            if chars.peek().is_some() {
+                if let Some(j) = buffer.iter().position(|&c| c == b'=') {
+                    return Err(DecodeError::InvalidByte(offsets[j], b'='));
+                }
+            }
+
+            f(&buffer[..len]).map_err(|err| remap_offsets(err, &offsets))?;
+
+            len = 0;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates `input` with `validate`, skipping ASCII whitespace, returning the decoded length
+///
+/// As with `decode`, chunks of 256 characters are validated independently through a stack
+/// buffer, so nothing is allocated.
+pub fn validate<F>(input: &[u8], validate: F) -> Result<usize, DecodeError>
+where
+    F: Fn(&[u8]) -> Result<usize, DecodeError>,
+{
+    let mut decoded_len = 0;
+
+    decode_chunks(input, |chunk| {
+        decoded_len += validate(chunk)?;
+
+        Ok(())
+    })?;
+
+    Ok(decoded_len)
+}
+
+/// Decodes `input` with `decode`, skipping ASCII whitespace, through a cleaned copy of the input
+///
+/// Error offsets are reported relative to the original `input`.
+pub fn decode_cleaned<F>(input: &[u8], output: &mut Vec<u8>, decode: F) -> Result<(), DecodeError>
+where
+    F: Fn(&[u8], &mut Vec<u8>) -> Result<(), DecodeError>,
+{
+    let mut cleaned = Vec::with_capacity(input.len());
+    let mut offsets = Vec::with_capacity(input.len());
+
+    for (i, &c) in input.iter().enumerate() {
+        if !c.is_ascii_whitespace() {
+            cleaned.push(c);
+            offsets.push(i);
+        }
+    }
+
+    decode(&cleaned, output).map_err(|err| remap_offsets(err, &offsets))
+}
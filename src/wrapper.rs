@@ -0,0 +1,189 @@
+//! Encoding for any byte container, without copying into a `Blob`
+
+use alloc_crate::string::String;
+use core::cmp::Ordering;
+use core::fmt::{self, Display};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use std::io;
+
+#[cfg(feature = "serde")]
+use serde_crate as serde;
+
+#[cfg(feature = "serde")]
+use super::serde::{deserialize_as, serialize_as, FromBytes};
+use super::{Blob, Config, Standard};
+
+/// Wrapper encoding any byte container `T` as a `Blob<C>` would
+///
+/// This works for `&[u8]`, `[u8; N]`, `Arc<[u8]>`, `bytes::Bytes` or anything else that
+/// implements `AsRef<[u8]>`, providing `Display`, `encode_to` and `Serialize` without moving
+/// the bytes into a `Vec<u8>`. It implements `Deserialize` for the containers supported by
+/// the [`serde`](serde/index.html) helper modules.
+///
+/// ```
+/// extern crate blob;
+///
+/// use std::sync::Arc;
+///
+/// use blob::{Base64, UrlSafeNoPad};
+///
+/// fn main() {
+///     let shared: Arc<[u8]> = Arc::from(&[0xfb, 0xff][..]);
+///
+///     assert_eq!(Base64::<_>::new(&[1, 2, 3]).to_string(), "AQID");
+///     assert_eq!(Base64::<_, UrlSafeNoPad>::new(shared).to_string(), "-_8");
+/// }
+/// ```
+pub struct Base64<T, C: Config = Standard> {
+    bytes: T,
+    _config: PhantomData<fn() -> C>,
+}
+
+/// Borrowed slice of bytes, serialized and displayed as a `Blob<C>` would be
+///
+/// Useful for encoding data only available by reference, such as part of a larger buffer.
+/// `Blob::as_blob_ref` borrows a whole blob as one.
+pub type BlobRef<'a, C = Standard> = Base64<&'a [u8], C>;
+
+impl<T: AsRef<[u8]>, C: Config> Base64<T, C> {
+    /// Wrap the given bytes
+    #[inline]
+    pub fn new(bytes: T) -> Base64<T, C> {
+        Base64 {
+            bytes,
+            _config: PhantomData,
+        }
+    }
+
+    /// Encode the bytes to a string, using base-64 or whichever encoding `C` specifies
+    pub fn encode_base64(&self) -> String {
+        let mut encoded = String::new();
+
+        C::encode(self.bytes.as_ref(), &mut encoded);
+
+        encoded
+    }
+
+    /// Encodes the bytes to an `io::Writer`, avoiding intermediate allocations for base-64 configs
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        C::encode_to(self.bytes.as_ref(), writer)
+    }
+
+    /// Consume self and return the inner bytes
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.bytes
+    }
+}
+
+impl<T: Clone, C: Config> Clone for Base64<T, C> {
+    #[inline]
+    fn clone(&self) -> Base64<T, C> {
+        Base64 {
+            bytes: self.bytes.clone(),
+            _config: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy, C: Config> Copy for Base64<T, C> {}
+
+impl<T: fmt::Debug, C: Config> fmt::Debug for Base64<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Base64").field(&self.bytes).finish()
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> Display for Base64<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        C::fmt(self.bytes.as_ref(), f)
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> PartialEq for Base64<T, C> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes.as_ref() == other.bytes.as_ref()
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> Eq for Base64<T, C> {}
+
+impl<T: AsRef<[u8]>, C: Config> PartialEq<Blob<C>> for Base64<T, C> {
+    #[inline(always)]
+    fn eq(&self, other: &Blob<C>) -> bool {
+        self.bytes.as_ref() == &other[..]
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> PartialOrd for Base64<T, C> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> Ord for Base64<T, C> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.as_ref().cmp(other.bytes.as_ref())
+    }
+}
+
+/// Hashes the same as the equivalent `Blob<C>`
+impl<T: AsRef<[u8]>, C: Config> Hash for Base64<T, C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.as_ref().hash(state);
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> Deref for Base64<T, C> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.bytes
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> AsRef<[u8]> for Base64<T, C> {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+}
+
+impl<T: AsRef<[u8]>, C: Config> From<T> for Base64<T, C> {
+    #[inline(always)]
+    fn from(bytes: T) -> Base64<T, C> {
+        Base64::new(bytes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: AsRef<[u8]>, C: Config> serde::Serialize for Base64<T, C> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_as::<C, T, S>(&self.bytes, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: AsRef<[u8]> + FromBytes<'de>, C: Config> serde::Deserialize<'de> for Base64<T, C> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_as::<C, T, D>(deserializer).map(Base64::new)
+    }
+}